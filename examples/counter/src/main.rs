@@ -1,5 +1,5 @@
 use iced::widget::{button, checkbox, column, text};
-use iced::{Center, Element};
+use iced::{Center, Element, Task};
 
 fn main() -> iced::Result {
     tracing_subscriber::fmt::init();
@@ -32,10 +32,17 @@ struct Counter {
 }
 
 impl Counter {
-    fn new() -> (Self, iced::Task<Message>) {
+    fn new() -> (Self, Task<Message>) {
         (
             Self::default(),
-            thawing::thaw::<Message>(ID).map(|_| Message::Reloaded),
+            thawing::thaw::<Message>(ID).then(|event| match event {
+                thawing::Event::Reloaded(_) => Task::done(Message::Reloaded),
+                thawing::Event::Failed(error) => {
+                    tracing::error!("{error}");
+                    Task::none()
+                }
+                _ => Task::none(),
+            }),
         )
     }
 
@@ -50,6 +57,8 @@ impl Counter {
         }
     }
 
+    // The whole body can be thawed instead, by annotating the method with
+    // `#[thawing::view_fn(id = ID)]` and returning the `column!` as an `Element`.
     fn view(&self) -> Element<Message> {
         thawing::view![
             column![
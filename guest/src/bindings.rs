@@ -201,6 +201,71 @@ pub mod thawing {
                     }
                 }
             }
+            #[repr(u8)]
+            #[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
+            pub enum Codec {
+                Bincode,
+                Postcard,
+                Json,
+            }
+            impl ::core::fmt::Debug for Codec {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        Codec::Bincode => f.debug_tuple("Codec::Bincode").finish(),
+                        Codec::Postcard => f.debug_tuple("Codec::Postcard").finish(),
+                        Codec::Json => f.debug_tuple("Codec::Json").finish(),
+                    }
+                }
+            }
+            impl Codec {
+                #[doc(hidden)]
+                pub unsafe fn _lift(val: u8) -> Codec {
+                    if !cfg!(debug_assertions) {
+                        return ::core::mem::transmute(val);
+                    }
+                    match val {
+                        0 => Codec::Bincode,
+                        1 => Codec::Postcard,
+                        2 => Codec::Json,
+                        _ => panic!("invalid enum discriminant"),
+                    }
+                }
+            }
+            #[derive(Clone)]
+            pub enum Literal {
+                Integer(i64),
+                Float(f64),
+                Text(_rt::String),
+                Character(char),
+                Boolean(bool),
+            }
+            impl ::core::fmt::Debug for Literal {
+                fn fmt(
+                    &self,
+                    f: &mut ::core::fmt::Formatter<'_>,
+                ) -> ::core::fmt::Result {
+                    match self {
+                        Literal::Integer(e) => {
+                            f.debug_tuple("Literal::Integer").field(e).finish()
+                        }
+                        Literal::Float(e) => {
+                            f.debug_tuple("Literal::Float").field(e).finish()
+                        }
+                        Literal::Text(e) => {
+                            f.debug_tuple("Literal::Text").field(e).finish()
+                        }
+                        Literal::Character(e) => {
+                            f.debug_tuple("Literal::Character").field(e).finish()
+                        }
+                        Literal::Boolean(e) => {
+                            f.debug_tuple("Literal::Boolean").field(e).finish()
+                        }
+                    }
+                }
+            }
             impl Closure {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn new() -> Self {
@@ -248,6 +313,7 @@ pub mod thawing {
             use super::super::super::_rt;
             pub type Element = super::super::super::thawing::core::types::Element;
             pub type Closure = super::super::super::thawing::core::types::Closure;
+            pub type Bytes = super::super::super::thawing::core::types::Bytes;
             pub type Pixels = super::super::super::thawing::core::types::Pixels;
             pub type Padding = super::super::super::thawing::core::types::Padding;
             pub type Length = super::super::super::thawing::core::types::Length;
@@ -401,6 +467,43 @@ pub mod thawing {
                     }
                 }
             }
+            #[derive(Debug)]
+            #[repr(transparent)]
+            pub struct Custom {
+                handle: _rt::Resource<Custom>,
+            }
+            impl Custom {
+                #[doc(hidden)]
+                pub unsafe fn from_handle(handle: u32) -> Self {
+                    Self {
+                        handle: unsafe { _rt::Resource::from_handle(handle) },
+                    }
+                }
+                #[doc(hidden)]
+                pub fn take_handle(&self) -> u32 {
+                    _rt::Resource::take_handle(&self.handle)
+                }
+                #[doc(hidden)]
+                pub fn handle(&self) -> u32 {
+                    _rt::Resource::handle(&self.handle)
+                }
+            }
+            unsafe impl _rt::WasmResource for Custom {
+                #[inline]
+                unsafe fn drop(_handle: u32) {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    unreachable!();
+                    #[cfg(target_arch = "wasm32")]
+                    {
+                        #[link(wasm_import_module = "thawing:core/widget")]
+                        unsafe extern "C" {
+                            #[link_name = "[resource-drop]custom"]
+                            fn drop(_: u32);
+                        }
+                        unsafe { drop(_handle) };
+                    }
+                }
+            }
             impl Button {
                 #[allow(unused_unsafe, clippy::all)]
                 pub fn new(content: Element) -> Self {
@@ -1044,6 +1147,100 @@ pub mod thawing {
                     }
                 }
             }
+            impl Custom {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn new(name: &str, props: &[u8]) -> Self {
+                    unsafe {
+                        let vec0 = name;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        let vec1 = props;
+                        let ptr1 = vec1.as_ptr().cast::<u8>();
+                        let len1 = vec1.len();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "thawing:core/widget")]
+                        unsafe extern "C" {
+                            #[link_name = "[constructor]custom"]
+                            fn wit_import2(
+                                _: *mut u8,
+                                _: usize,
+                                _: *mut u8,
+                                _: usize,
+                            ) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import2(
+                            _: *mut u8,
+                            _: usize,
+                            _: *mut u8,
+                            _: usize,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import2(ptr0.cast_mut(), len0, ptr1.cast_mut(), len1)
+                        };
+                        unsafe { Custom::from_handle(ret as u32) }
+                    }
+                }
+            }
+            impl Custom {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn on(&self, event: &str, closure: Closure) -> Custom {
+                    unsafe {
+                        let vec0 = event;
+                        let ptr0 = vec0.as_ptr().cast::<u8>();
+                        let len0 = vec0.len();
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "thawing:core/widget")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]custom.on"]
+                            fn wit_import1(_: i32, _: *mut u8, _: usize, _: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import1(
+                            _: i32,
+                            _: *mut u8,
+                            _: usize,
+                            _: i32,
+                        ) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe {
+                            wit_import1(
+                                (self).handle() as i32,
+                                ptr0.cast_mut(),
+                                len0,
+                                (&closure).take_handle() as i32,
+                            )
+                        };
+                        unsafe { Custom::from_handle(ret as u32) }
+                    }
+                }
+            }
+            impl Custom {
+                #[allow(unused_unsafe, clippy::all)]
+                pub fn into_element(&self) -> Element {
+                    unsafe {
+                        #[cfg(target_arch = "wasm32")]
+                        #[link(wasm_import_module = "thawing:core/widget")]
+                        unsafe extern "C" {
+                            #[link_name = "[method]custom.into-element"]
+                            fn wit_import0(_: i32) -> i32;
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        unsafe extern "C" fn wit_import0(_: i32) -> i32 {
+                            unreachable!()
+                        }
+                        let ret = unsafe { wit_import0((self).handle() as i32) };
+                        unsafe {
+                            super::super::super::thawing::core::types::Element::from_handle(
+                                ret as u32,
+                            )
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -1061,6 +1258,8 @@ pub mod exports {
                 pub type Element = super::super::super::super::thawing::core::types::Element;
                 pub type Closure = super::super::super::super::thawing::core::types::Closure;
                 pub type Bytes = super::super::super::super::thawing::core::types::Bytes;
+                pub type Literal = super::super::super::super::thawing::core::types::Literal;
+                pub type Codec = super::super::super::super::thawing::core::types::Codec;
                 #[derive(Debug)]
                 #[repr(transparent)]
                 pub struct Table {
@@ -1303,6 +1502,278 @@ pub mod exports {
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
+                pub unsafe fn _export_set_codec_cabi<T: Guest>(arg0: i32) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    T::set_codec(
+                        super::super::super::super::thawing::core::types::Codec::_lift(
+                            arg0 as u8,
+                        ),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_set_literals_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                ) {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let base9 = arg0;
+                    let len9 = arg1;
+                    let mut result9 = _rt::Vec::with_capacity(len9);
+                    for i in 0..len9 {
+                        let base = base9
+                            .add(i * (8 + 2 * ::core::mem::size_of::<*const u8>()));
+                        let e9 = {
+                            let l0 = i32::from(*base.add(0).cast::<u8>());
+                            use super::super::super::super::thawing::core::types::Literal as V8;
+                            let v8 = match l0 {
+                                0 => {
+                                    let e8 = {
+                                        let l1 = *base.add(8).cast::<i64>();
+                                        l1
+                                    };
+                                    V8::Integer(e8)
+                                }
+                                1 => {
+                                    let e8 = {
+                                        let l2 = *base.add(8).cast::<f64>();
+                                        l2
+                                    };
+                                    V8::Float(e8)
+                                }
+                                2 => {
+                                    let e8 = {
+                                        let l3 = *base.add(8).cast::<*mut u8>();
+                                        let l4 = *base
+                                            .add(8 + 1 * ::core::mem::size_of::<*const u8>())
+                                            .cast::<usize>();
+                                        let len5 = l4;
+                                        let bytes5 = _rt::Vec::from_raw_parts(
+                                            l3.cast(),
+                                            len5,
+                                            len5,
+                                        );
+                                        _rt::string_lift(bytes5)
+                                    };
+                                    V8::Text(e8)
+                                }
+                                3 => {
+                                    let e8 = {
+                                        let l6 = *base.add(8).cast::<i32>();
+                                        _rt::char_lift(l6 as u32)
+                                    };
+                                    V8::Character(e8)
+                                }
+                                n => {
+                                    debug_assert_eq!(n, 4, "invalid enum discriminant");
+                                    let e8 = {
+                                        let l7 = i32::from(*base.add(8).cast::<u8>());
+                                        _rt::bool_lift(l7 as u8)
+                                    };
+                                    V8::Boolean(e8)
+                                }
+                            };
+                            v8
+                        };
+                        result9.push(e9);
+                    }
+                    _rt::cabi_dealloc(
+                        base9,
+                        len9 * (8 + 2 * ::core::mem::size_of::<*const u8>()),
+                        8,
+                    );
+                    T::set_literals(result9);
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_message_fingerprint_cabi<T: Guest>() -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::message_fingerprint();
+                    let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    let vec5 = result0;
+                    let len5 = vec5.len();
+                    let layout5 = _rt::alloc::Layout::from_size_align_unchecked(
+                        vec5.len() * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                    let result5 = if layout5.size() != 0 {
+                        let ptr = _rt::alloc::alloc(layout5).cast::<u8>();
+                        if ptr.is_null() {
+                            _rt::alloc::handle_alloc_error(layout5);
+                        }
+                        ptr
+                    } else {
+                        ::core::ptr::null_mut()
+                    };
+                    for (i, e) in vec5.into_iter().enumerate() {
+                        let base = result5
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let (t2_0, t2_1) = e;
+                            let vec3 = (t2_0.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *base.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                            let vec4 = (t2_1.into_bytes()).into_boxed_slice();
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            ::core::mem::forget(vec4);
+                            *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
+                            *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    }
+                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len5;
+                    *ptr1.add(0).cast::<*mut u8>() = result5;
+                    ptr1
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_message_fingerprint<T: Guest>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = *arg0.add(0).cast::<*mut u8>();
+                    let l1 = *arg0
+                        .add(::core::mem::size_of::<*const u8>())
+                        .cast::<usize>();
+                    let base6 = l0;
+                    let len6 = l1;
+                    for i in 0..len6 {
+                        let base = base6
+                            .add(i * (4 * ::core::mem::size_of::<*const u8>()));
+                        {
+                            let l2 = *base.add(0).cast::<*mut u8>();
+                            let l3 = *base
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l2, l3, 1);
+                            let l4 = *base
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *base
+                                .add(3 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l4, l5, 1);
+                        }
+                    }
+                    _rt::cabi_dealloc(
+                        base6,
+                        len6 * (4 * ::core::mem::size_of::<*const u8>()),
+                        ::core::mem::size_of::<*const u8>(),
+                    );
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_has_update_cabi<T: Guest>() -> i32 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let result0 = T::has_update();
+                    match result0 {
+                        true => 1,
+                        false => 0,
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_update_cabi<T: Guest>(
+                    arg0: *mut u8,
+                    arg1: usize,
+                    arg2: *mut u8,
+                    arg3: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg1;
+                    let len1 = arg3;
+                    let result2 = T::update(
+                        _rt::Vec::from_raw_parts(arg0.cast(), len0, len0),
+                        _rt::Vec::from_raw_parts(arg2.cast(), len1, len1),
+                    );
+                    let ptr3 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result2 {
+                        Ok(e) => {
+                            *ptr3.add(0).cast::<u8>() = (0i32) as u8;
+                            match e {
+                                Some(e) => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (1i32) as u8;
+                                    let vec4 = (e).into_boxed_slice();
+                                    let ptr4 = vec4.as_ptr().cast::<u8>();
+                                    let len4 = vec4.len();
+                                    ::core::mem::forget(vec4);
+                                    *ptr3
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>() = len4;
+                                    *ptr3
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>() = ptr4.cast_mut();
+                                }
+                                None => {
+                                    *ptr3
+                                        .add(::core::mem::size_of::<*const u8>())
+                                        .cast::<u8>() = (0i32) as u8;
+                                }
+                            };
+                        }
+                        Err(e) => {
+                            *ptr3.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec5 = (e.into_bytes()).into_boxed_slice();
+                            let ptr5 = vec5.as_ptr().cast::<u8>();
+                            let len5 = vec5.len();
+                            ::core::mem::forget(vec5);
+                            *ptr3
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len5;
+                            *ptr3
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr5.cast_mut();
+                        }
+                    };
+                    ptr3
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_update<T: Guest>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = i32::from(
+                                *arg0.add(::core::mem::size_of::<*const u8>()).cast::<u8>(),
+                            );
+                            match l1 {
+                                0 => {}
+                                _ => {
+                                    let l2 = *arg0
+                                        .add(2 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<*mut u8>();
+                                    let l3 = *arg0
+                                        .add(3 * ::core::mem::size_of::<*const u8>())
+                                        .cast::<usize>();
+                                    let base4 = l2;
+                                    let len4 = l3;
+                                    _rt::cabi_dealloc(base4, len4 * 1, 1);
+                                }
+                            }
+                        }
+                        _ => {
+                            let l5 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l6 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l5, l6, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
                 pub unsafe fn _export_constructor_table_cabi<T: GuestTable>() -> i32 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let result0 = Table::new(T::new());
@@ -1324,12 +1795,34 @@ pub mod exports {
                         },
                     );
                     let ptr1 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec2 = (result0).into_boxed_slice();
-                    let ptr2 = vec2.as_ptr().cast::<u8>();
-                    let len2 = vec2.len();
-                    ::core::mem::forget(vec2);
-                    *ptr1.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len2;
-                    *ptr1.add(0).cast::<*mut u8>() = ptr2.cast_mut();
+                    match result0 {
+                        Ok(e) => {
+                            *ptr1.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec2 = (e).into_boxed_slice();
+                            let ptr2 = vec2.as_ptr().cast::<u8>();
+                            let len2 = vec2.len();
+                            ::core::mem::forget(vec2);
+                            *ptr1
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len2;
+                            *ptr1
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr2.cast_mut();
+                        }
+                        Err(e) => {
+                            *ptr1.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr1
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr1
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
                     ptr1
                 }
                 #[doc(hidden)]
@@ -1337,13 +1830,29 @@ pub mod exports {
                 pub unsafe fn __post_return_method_table_call<T: GuestTable>(
                     arg0: *mut u8,
                 ) {
-                    let l0 = *arg0.add(0).cast::<*mut u8>();
-                    let l1 = *arg0
-                        .add(::core::mem::size_of::<*const u8>())
-                        .cast::<usize>();
-                    let base2 = l0;
-                    let len2 = l1;
-                    _rt::cabi_dealloc(base2, len2 * 1, 1);
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base3 = l1;
+                            let len3 = l2;
+                            _rt::cabi_dealloc(base3, len3 * 1, 1);
+                        }
+                        _ => {
+                            let l4 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l4, l5, 1);
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -1365,12 +1874,34 @@ pub mod exports {
                         _rt::Vec::from_raw_parts(arg2.cast(), len0, len0),
                     );
                     let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
-                    let vec3 = (result1).into_boxed_slice();
-                    let ptr3 = vec3.as_ptr().cast::<u8>();
-                    let len3 = vec3.len();
-                    ::core::mem::forget(vec3);
-                    *ptr2.add(::core::mem::size_of::<*const u8>()).cast::<usize>() = len3;
-                    *ptr2.add(0).cast::<*mut u8>() = ptr3.cast_mut();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            let vec3 = (e).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec4 = (e.into_bytes()).into_boxed_slice();
+                            let ptr4 = vec4.as_ptr().cast::<u8>();
+                            let len4 = vec4.len();
+                            ::core::mem::forget(vec4);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len4;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr4.cast_mut();
+                        }
+                    };
                     ptr2
                 }
                 #[doc(hidden)]
@@ -1378,26 +1909,134 @@ pub mod exports {
                 pub unsafe fn __post_return_method_table_call_with<T: GuestTable>(
                     arg0: *mut u8,
                 ) {
-                    let l0 = *arg0.add(0).cast::<*mut u8>();
-                    let l1 = *arg0
-                        .add(::core::mem::size_of::<*const u8>())
-                        .cast::<usize>();
-                    let base2 = l0;
-                    let len2 = l1;
-                    _rt::cabi_dealloc(base2, len2 * 1, 1);
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            let base3 = l1;
+                            let len3 = l2;
+                            _rt::cabi_dealloc(base3, len3 * 1, 1);
+                        }
+                        _ => {
+                            let l4 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l5 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l4, l5, 1);
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
-                pub unsafe fn _export_constructor_app_cabi<T: GuestApp>(
+                pub unsafe fn _export_static_app_load_cabi<T: GuestApp>(
                     arg0: *mut u8,
                     arg1: usize,
-                ) -> i32 {
+                ) -> *mut u8 {
                     #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
                     let len0 = arg1;
-                    let result1 = App::new(
-                        T::new(_rt::Vec::from_raw_parts(arg0.cast(), len0, len0)),
+                    let result1 = T::load(
+                        _rt::Vec::from_raw_parts(arg0.cast(), len0, len0),
+                    );
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(e) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<i32>() = (e).take_handle() as i32;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_static_app_load<T: GuestApp>(arg0: *mut u8) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn _export_method_app_set_state_cabi<T: GuestApp>(
+                    arg0: *mut u8,
+                    arg1: *mut u8,
+                    arg2: usize,
+                ) -> *mut u8 {
+                    #[cfg(target_arch = "wasm32")] _rt::run_ctors_once();
+                    let len0 = arg2;
+                    let result1 = T::set_state(
+                        unsafe { AppBorrow::lift(arg0 as u32 as usize) }.get(),
+                        _rt::Vec::from_raw_parts(arg1.cast(), len0, len0),
                     );
-                    (result1).take_handle() as i32
+                    let ptr2 = (&raw mut _RET_AREA.0).cast::<u8>();
+                    match result1 {
+                        Ok(_) => {
+                            *ptr2.add(0).cast::<u8>() = (0i32) as u8;
+                        }
+                        Err(e) => {
+                            *ptr2.add(0).cast::<u8>() = (1i32) as u8;
+                            let vec3 = (e.into_bytes()).into_boxed_slice();
+                            let ptr3 = vec3.as_ptr().cast::<u8>();
+                            let len3 = vec3.len();
+                            ::core::mem::forget(vec3);
+                            *ptr2
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>() = len3;
+                            *ptr2
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>() = ptr3.cast_mut();
+                        }
+                    };
+                    ptr2
+                }
+                #[doc(hidden)]
+                #[allow(non_snake_case)]
+                pub unsafe fn __post_return_method_app_set_state<T: GuestApp>(
+                    arg0: *mut u8,
+                ) {
+                    let l0 = i32::from(*arg0.add(0).cast::<u8>());
+                    match l0 {
+                        0 => {}
+                        _ => {
+                            let l1 = *arg0
+                                .add(::core::mem::size_of::<*const u8>())
+                                .cast::<*mut u8>();
+                            let l2 = *arg0
+                                .add(2 * ::core::mem::size_of::<*const u8>())
+                                .cast::<usize>();
+                            _rt::cabi_dealloc(l1, l2, 1);
+                        }
+                    }
                 }
                 #[doc(hidden)]
                 #[allow(non_snake_case)]
@@ -1413,6 +2052,14 @@ pub mod exports {
                 pub trait Guest {
                     type Table: GuestTable;
                     type App: GuestApp;
+                    fn set_codec(codec: Codec) -> ();
+                    fn set_literals(literals: _rt::Vec<Literal>) -> ();
+                    fn message_fingerprint() -> _rt::Vec<(_rt::String, _rt::String)>;
+                    fn has_update() -> bool;
+                    fn update(
+                        state: Bytes,
+                        message: Bytes,
+                    ) -> Result<Option<Bytes>, _rt::String>;
                 }
                 pub trait GuestTable: 'static {
                     #[doc(hidden)]
@@ -1456,8 +2103,12 @@ pub mod exports {
                         }
                     }
                     fn new() -> Self;
-                    fn call(&self, c: Closure) -> Bytes;
-                    fn call_with(&self, c: Closure, state: Bytes) -> Bytes;
+                    fn call(&self, c: Closure) -> Result<Bytes, _rt::String>;
+                    fn call_with(
+                        &self,
+                        c: Closure,
+                        state: Bytes,
+                    ) -> Result<Bytes, _rt::String>;
                 }
                 pub trait GuestApp: 'static {
                     #[doc(hidden)]
@@ -1500,7 +2151,8 @@ pub mod exports {
                             unsafe { rep(handle) }
                         }
                     }
-                    fn new(state: Bytes) -> Self;
+                    fn load(state: Bytes) -> Result<App, _rt::String>;
+                    fn set_state(&self, state: Bytes) -> Result<(), _rt::String>;
                     fn view(&self) -> Element;
                 }
                 #[doc(hidden)]
@@ -1508,13 +2160,38 @@ pub mod exports {
                 macro_rules! __export_thawing_core_guest_cabi {
                     ($ty:ident with_types_in $($path_to_types:tt)*) => {
                         const _ : () = { #[unsafe (export_name =
-                        "thawing:core/guest#[constructor]table")] unsafe extern "C" fn
-                        export_constructor_table() -> i32 { unsafe { $($path_to_types)*::
-                        _export_constructor_table_cabi::<<$ty as $($path_to_types)*::
-                        Guest >::Table > () } } #[unsafe (export_name =
-                        "thawing:core/guest#[method]table.call")] unsafe extern "C" fn
-                        export_method_table_call(arg0 : * mut u8, arg1 : i32,) -> * mut
-                        u8 { unsafe { $($path_to_types)*::
+                        "thawing:core/guest#set-codec")] unsafe extern "C" fn
+                        export_set_codec(arg0 : i32,) { unsafe { $($path_to_types)*::
+                        _export_set_codec_cabi::<$ty > (arg0) } } #[unsafe (export_name =
+                        "thawing:core/guest#set-literals")] unsafe extern "C" fn
+                        export_set_literals(arg0 : * mut u8, arg1 : usize,) { unsafe {
+                        $($path_to_types)*:: _export_set_literals_cabi::<$ty > (arg0,
+                        arg1) } } #[unsafe (export_name =
+                        "thawing:core/guest#message-fingerprint")] unsafe extern "C" fn
+                        export_message_fingerprint() -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_message_fingerprint_cabi::<$ty > ()
+                        } } #[unsafe (export_name =
+                        "cabi_post_thawing:core/guest#message-fingerprint")] unsafe
+                        extern "C" fn _post_return_message_fingerprint(arg0 : * mut u8,)
+                        { unsafe { $($path_to_types)*::
+                        __post_return_message_fingerprint::<$ty > (arg0) } } #[unsafe
+                        (export_name = "thawing:core/guest#has-update")] unsafe extern
+                        "C" fn export_has_update() -> i32 { unsafe { $($path_to_types)*::
+                        _export_has_update_cabi::<$ty > () } } #[unsafe (export_name =
+                        "thawing:core/guest#update")] unsafe extern "C" fn
+                        export_update(arg0 : * mut u8, arg1 : usize, arg2 : * mut u8,
+                        arg3 : usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_update_cabi::<$ty > (arg0, arg1, arg2, arg3) } } #[unsafe
+                        (export_name = "cabi_post_thawing:core/guest#update")] unsafe
+                        extern "C" fn _post_return_update(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_update::<$ty > (arg0) } }
+                        #[unsafe (export_name = "thawing:core/guest#[constructor]table")]
+                        unsafe extern "C" fn export_constructor_table() -> i32 { unsafe {
+                        $($path_to_types)*:: _export_constructor_table_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::Table > () } } #[unsafe
+                        (export_name = "thawing:core/guest#[method]table.call")] unsafe
+                        extern "C" fn export_method_table_call(arg0 : * mut u8, arg1 :
+                        i32,) -> * mut u8 { unsafe { $($path_to_types)*::
                         _export_method_table_call_cabi::<<$ty as $($path_to_types)*::
                         Guest >::Table > (arg0, arg1) } } #[unsafe (export_name =
                         "cabi_post_thawing:core/guest#[method]table.call")] unsafe extern
@@ -1533,11 +2210,26 @@ pub mod exports {
                         u8,) { unsafe { $($path_to_types)*::
                         __post_return_method_table_call_with::<<$ty as
                         $($path_to_types)*:: Guest >::Table > (arg0) } } #[unsafe
-                        (export_name = "thawing:core/guest#[constructor]app")] unsafe
-                        extern "C" fn export_constructor_app(arg0 : * mut u8, arg1 :
-                        usize,) -> i32 { unsafe { $($path_to_types)*::
-                        _export_constructor_app_cabi::<<$ty as $($path_to_types)*:: Guest
+                        (export_name = "thawing:core/guest#[static]app.load")] unsafe
+                        extern "C" fn export_static_app_load(arg0 : * mut u8, arg1 :
+                        usize,) -> * mut u8 { unsafe { $($path_to_types)*::
+                        _export_static_app_load_cabi::<<$ty as $($path_to_types)*:: Guest
                         >::App > (arg0, arg1) } } #[unsafe (export_name =
+                        "cabi_post_thawing:core/guest#[static]app.load")] unsafe extern
+                        "C" fn _post_return_static_app_load(arg0 : * mut u8,) { unsafe {
+                        $($path_to_types)*:: __post_return_static_app_load::<<$ty as
+                        $($path_to_types)*:: Guest >::App > (arg0) } } #[unsafe
+                        (export_name = "thawing:core/guest#[method]app.set-state")]
+                        unsafe extern "C" fn export_method_app_set_state(arg0 : * mut u8,
+                        arg1 : * mut u8, arg2 : usize,) -> * mut u8 { unsafe {
+                        $($path_to_types)*:: _export_method_app_set_state_cabi::<<$ty as
+                        $($path_to_types)*:: Guest >::App > (arg0, arg1, arg2) } }
+                        #[unsafe (export_name =
+                        "cabi_post_thawing:core/guest#[method]app.set-state")] unsafe
+                        extern "C" fn _post_return_method_app_set_state(arg0 : * mut u8,)
+                        { unsafe { $($path_to_types)*::
+                        __post_return_method_app_set_state::<<$ty as $($path_to_types)*::
+                        Guest >::App > (arg0) } } #[unsafe (export_name =
                         "thawing:core/guest#[method]app.view")] unsafe extern "C" fn
                         export_method_app_view(arg0 : * mut u8,) -> i32 { unsafe {
                         $($path_to_types)*:: _export_method_app_view_cabi::<<$ty as
@@ -1560,10 +2252,10 @@ pub mod exports {
                 struct _RetArea(
                     [::core::mem::MaybeUninit<
                         u8,
-                    >; 2 * ::core::mem::size_of::<*const u8>()],
+                    >; 4 * ::core::mem::size_of::<*const u8>()],
                 );
                 static mut _RET_AREA: _RetArea = _RetArea(
-                    [::core::mem::MaybeUninit::uninit(); 2
+                    [::core::mem::MaybeUninit::uninit(); 4
                         * ::core::mem::size_of::<*const u8>()],
                 );
             }
@@ -1648,6 +2340,7 @@ mod _rt {
         }
     }
     pub use alloc_crate::vec::Vec;
+    pub use alloc_crate::string::String;
     pub use alloc_crate::alloc;
     pub fn as_f32<T: AsF32>(t: T) -> f32 {
         t.as_f32()
@@ -1730,6 +2423,31 @@ mod _rt {
     pub fn run_ctors_once() {
         wit_bindgen_rt::run_ctors_once();
     }
+    pub unsafe fn string_lift(bytes: Vec<u8>) -> String {
+        if cfg!(debug_assertions) {
+            String::from_utf8(bytes).unwrap()
+        } else {
+            String::from_utf8_unchecked(bytes)
+        }
+    }
+    pub unsafe fn char_lift(val: u32) -> char {
+        if cfg!(debug_assertions) {
+            core::char::from_u32(val).unwrap()
+        } else {
+            core::char::from_u32_unchecked(val)
+        }
+    }
+    pub unsafe fn bool_lift(val: u8) -> bool {
+        if cfg!(debug_assertions) {
+            match val {
+                0 => false,
+                1 => true,
+                _ => panic!("invalid bool discriminant"),
+            }
+        } else {
+            val != 0
+        }
+    }
     pub unsafe fn cabi_dealloc(ptr: *mut u8, size: usize, align: usize) {
         if size == 0 {
             return;
@@ -1769,56 +2487,69 @@ macro_rules! __export_thawing_impl {
         () = { #[cfg(target_arch = "wasm32")] #[unsafe (link_section =
         "component-type:wit-bindgen:0.41.0:thawing:core:thawing:imports and exports")]
         #[doc(hidden)] #[allow(clippy::octal_escapes)] pub static
-        __WIT_BINDGEN_COMPONENT_TYPE : [u8; 2093] = *
+        __WIT_BINDGEN_COMPONENT_TYPE : [u8; 2640] = *
         b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xaf\x0f\x01A\x02\x01\
-A\x0e\x01B\x14\x04\0\x07closure\x03\x01\x04\0\x07element\x03\x01\x01p}\x04\0\x05\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xd2\x13\x01A\x02\x01\
+A\x10\x01B\x18\x04\0\x07closure\x03\x01\x04\0\x07element\x03\x01\x01p}\x04\0\x05\
 bytes\x03\0\x02\x01r\x01\x06amountv\x04\0\x06pixels\x03\0\x04\x01r\x04\x01rv\x01\
 gv\x01bv\x01av\x04\0\x05color\x03\0\x06\x01r\x04\x03topv\x05rightv\x06bottomv\x04\
 leftv\x04\0\x07padding\x03\0\x08\x01q\x04\x04fill\0\0\x0cfill-portion\x01{\0\x06\
 shrink\0\0\x05fixed\x01v\0\x04\0\x06length\x03\0\x0a\x01m\x03\x04left\x06center\x05\
-right\x04\0\x0ahorizontal\x03\0\x0c\x01i\0\x01@\0\0\x0e\x04\0\x14[constructor]cl\
-osure\x01\x0f\x01h\0\x01@\x01\x04self\x10\0y\x04\0\x12[method]closure.id\x01\x11\
-\x03\0\x12thawing:core/types\x05\0\x02\x03\0\0\x07element\x02\x03\0\0\x07closure\
-\x02\x03\0\0\x06pixels\x02\x03\0\0\x07padding\x02\x03\0\0\x06length\x02\x03\0\0\x0a\
-horizontal\x02\x03\0\0\x05color\x01BK\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\
-\0\x02\x03\x02\x01\x02\x04\0\x07closure\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x06p\
-ixels\x03\0\x04\x02\x03\x02\x01\x04\x04\0\x07padding\x03\0\x06\x02\x03\x02\x01\x05\
-\x04\0\x06length\x03\0\x08\x02\x03\x02\x01\x06\x04\0\x0ahorizontal\x03\0\x0a\x02\
-\x03\x02\x01\x07\x04\0\x05color\x03\0\x0c\x04\0\x06button\x03\x01\x04\0\x08check\
-box\x03\x01\x04\0\x06column\x03\x01\x04\0\x04text\x03\x01\x01i\x01\x01i\x0e\x01@\
-\x01\x07content\x12\0\x13\x04\0\x13[constructor]button\x01\x14\x01h\x0e\x01i\x03\
-\x01@\x02\x04self\x15\x08on-press\x16\0\x13\x04\0\x1c[method]button.on-press-wit\
-h\x01\x17\x01@\x01\x04self\x15\0\x12\x04\0\x1b[method]button.into-element\x01\x18\
-\x01i\x0f\x01@\x02\x05labels\x0ais-checked\x7f\0\x19\x04\0\x15[constructor]check\
-box\x01\x1a\x01h\x0f\x01@\x02\x04self\x1b\x09on-toggle\x16\0\x19\x04\0\x1a[metho\
-d]checkbox.on-toggle\x01\x1c\x01@\x01\x04self\x1b\0\x12\x04\0\x1d[method]checkbo\
-x.into-element\x01\x1d\x01i\x10\x01@\0\0\x1e\x04\0\x13[constructor]column\x01\x1f\
-\x01p\x12\x01@\x01\x08children\x20\0\x1e\x04\0\x17[static]column.from-vec\x01!\x01\
-h\x10\x01@\x02\x04self\"\x06amount\x05\0\x1e\x04\0\x16[method]column.spacing\x01\
-#\x01@\x02\x04self\"\x07padding\x07\0\x1e\x04\0\x16[method]column.padding\x01$\x01\
-@\x02\x04self\"\x05width\x09\0\x1e\x04\0\x14[method]column.width\x01%\x01@\x02\x04\
-self\"\x06height\x09\0\x1e\x04\0\x15[method]column.height\x01&\x01@\x02\x04self\"\
-\x05width\x05\0\x1e\x04\0\x18[method]column.max-width\x01'\x01@\x02\x04self\"\x05\
-align\x0b\0\x1e\x04\0\x16[method]column.align-x\x01(\x01@\x02\x04self\"\x04clip\x7f\
-\0\x1e\x04\0\x13[method]column.clip\x01)\x01@\x02\x04self\"\x05child\x12\0\x1e\x04\
-\0\x13[method]column.push\x01*\x01@\x02\x04self\"\x08children\x20\0\x1e\x04\0\x15\
-[method]column.extend\x01+\x01@\x01\x04self\"\0\x12\x04\0\x1b[method]column.into\
--element\x01,\x01i\x11\x01@\x01\x08fragments\0-\x04\0\x11[constructor]text\x01.\x01\
-h\x11\x01@\x02\x04self/\x04size\x05\0-\x04\0\x11[method]text.size\x010\x01@\x02\x04\
-self/\x05color\x0d\0-\x04\0\x12[method]text.color\x011\x01@\x02\x04self/\x08styl\
-e-fn\x16\0-\x04\0\x12[method]text.style\x012\x01@\x01\x04self/\0\x12\x04\0\x19[m\
-ethod]text.into-element\x013\x03\0\x13thawing:core/widget\x05\x08\x02\x03\0\0\x05\
-bytes\x01B\x18\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\0\x02\x03\x02\x01\x02\x04\
-\0\x07closure\x03\0\x02\x02\x03\x02\x01\x09\x04\0\x05bytes\x03\0\x04\x04\0\x05ta\
-ble\x03\x01\x04\0\x03app\x03\x01\x01i\x06\x01@\0\0\x08\x04\0\x12[constructor]tab\
-le\x01\x09\x01h\x06\x01i\x03\x01@\x02\x04self\x0a\x01c\x0b\0\x05\x04\0\x12[metho\
-d]table.call\x01\x0c\x01@\x03\x04self\x0a\x01c\x0b\x05state\x05\0\x05\x04\0\x17[\
-method]table.call-with\x01\x0d\x01i\x07\x01@\x01\x05state\x05\0\x0e\x04\0\x10[co\
-nstructor]app\x01\x0f\x01h\x07\x01i\x01\x01@\x01\x04self\x10\0\x11\x04\0\x10[met\
-hod]app.view\x01\x12\x04\0\x12thawing:core/guest\x05\x0a\x04\0\x14thawing:core/t\
-hawing\x04\0\x0b\x0d\x01\0\x07thawing\x03\0\0\0G\x09producers\x01\x0cprocessed-b\
-y\x02\x0dwit-component\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+right\x04\0\x0ahorizontal\x03\0\x0c\x01m\x03\x07bincode\x08postcard\x04json\x04\0\
+\x05codec\x03\0\x0e\x01q\x05\x07integer\x01x\0\x05float\x01u\0\x04text\x01s\0\x09\
+character\x01t\0\x07boolean\x01\x7f\0\x04\0\x07literal\x03\0\x10\x01i\0\x01@\0\0\
+\x12\x04\0\x14[constructor]closure\x01\x13\x01h\0\x01@\x01\x04self\x14\0y\x04\0\x12\
+[method]closure.id\x01\x15\x03\0\x12thawing:core/types\x05\0\x02\x03\0\0\x07elem\
+ent\x02\x03\0\0\x07closure\x02\x03\0\0\x05bytes\x02\x03\0\0\x06pixels\x02\x03\0\0\
+\x07padding\x02\x03\0\0\x06length\x02\x03\0\0\x0ahorizontal\x02\x03\0\0\x05color\
+\x01BV\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\0\x02\x03\x02\x01\x02\x04\0\x07\
+closure\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x05bytes\x03\0\x04\x02\x03\x02\x01\x04\
+\x04\0\x06pixels\x03\0\x06\x02\x03\x02\x01\x05\x04\0\x07padding\x03\0\x08\x02\x03\
+\x02\x01\x06\x04\0\x06length\x03\0\x0a\x02\x03\x02\x01\x07\x04\0\x0ahorizontal\x03\
+\0\x0c\x02\x03\x02\x01\x08\x04\0\x05color\x03\0\x0e\x04\0\x06button\x03\x01\x04\0\
+\x08checkbox\x03\x01\x04\0\x06column\x03\x01\x04\0\x04text\x03\x01\x04\0\x06cust\
+om\x03\x01\x01i\x01\x01i\x10\x01@\x01\x07content\x15\0\x16\x04\0\x13[constructor\
+]button\x01\x17\x01h\x10\x01i\x03\x01@\x02\x04self\x18\x08on-press\x19\0\x16\x04\
+\0\x1c[method]button.on-press-with\x01\x1a\x01@\x01\x04self\x18\0\x15\x04\0\x1b[\
+method]button.into-element\x01\x1b\x01i\x11\x01@\x02\x05labels\x0ais-checked\x7f\
+\0\x1c\x04\0\x15[constructor]checkbox\x01\x1d\x01h\x11\x01@\x02\x04self\x1e\x09o\
+n-toggle\x19\0\x1c\x04\0\x1a[method]checkbox.on-toggle\x01\x1f\x01@\x01\x04self\x1e\
+\0\x15\x04\0\x1d[method]checkbox.into-element\x01\x20\x01i\x12\x01@\0\0!\x04\0\x13\
+[constructor]column\x01\"\x01p\x15\x01@\x01\x08children#\0!\x04\0\x17[static]col\
+umn.from-vec\x01$\x01h\x12\x01@\x02\x04self%\x06amount\x07\0!\x04\0\x16[method]c\
+olumn.spacing\x01&\x01@\x02\x04self%\x07padding\x09\0!\x04\0\x16[method]column.p\
+adding\x01'\x01@\x02\x04self%\x05width\x0b\0!\x04\0\x14[method]column.width\x01(\
+\x01@\x02\x04self%\x06height\x0b\0!\x04\0\x15[method]column.height\x01)\x01@\x02\
+\x04self%\x05width\x07\0!\x04\0\x18[method]column.max-width\x01*\x01@\x02\x04sel\
+f%\x05align\x0d\0!\x04\0\x16[method]column.align-x\x01+\x01@\x02\x04self%\x04cli\
+p\x7f\0!\x04\0\x13[method]column.clip\x01,\x01@\x02\x04self%\x05child\x15\0!\x04\
+\0\x13[method]column.push\x01-\x01@\x02\x04self%\x08children#\0!\x04\0\x15[metho\
+d]column.extend\x01.\x01@\x01\x04self%\0\x15\x04\0\x1b[method]column.into-elemen\
+t\x01/\x01i\x13\x01@\x01\x08fragments\00\x04\0\x11[constructor]text\x011\x01h\x13\
+\x01@\x02\x04self2\x04size\x07\00\x04\0\x11[method]text.size\x013\x01@\x02\x04se\
+lf2\x05color\x0f\00\x04\0\x12[method]text.color\x014\x01@\x02\x04self2\x08style-\
+fn\x19\00\x04\0\x12[method]text.style\x015\x01@\x01\x04self2\0\x15\x04\0\x19[met\
+hod]text.into-element\x016\x01i\x14\x01@\x02\x04names\x05props\x05\07\x04\0\x13[\
+constructor]custom\x018\x01h\x14\x01@\x03\x04self9\x05events\x07closure\x19\07\x04\
+\0\x11[method]custom.on\x01:\x01@\x01\x04self9\0\x15\x04\0\x1b[method]custom.int\
+o-element\x01;\x03\0\x13thawing:core/widget\x05\x09\x02\x03\0\0\x07literal\x02\x03\
+\0\0\x05codec\x01B0\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\0\x02\x03\x02\x01\
+\x02\x04\0\x07closure\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x05bytes\x03\0\x04\x02\
+\x03\x02\x01\x0a\x04\0\x07literal\x03\0\x06\x02\x03\x02\x01\x0b\x04\0\x05codec\x03\
+\0\x08\x04\0\x05table\x03\x01\x04\0\x03app\x03\x01\x01i\x0a\x01@\0\0\x0c\x04\0\x12\
+[constructor]table\x01\x0d\x01h\x0a\x01i\x03\x01j\x01\x05\x01s\x01@\x02\x04self\x0e\
+\x01c\x0f\0\x10\x04\0\x12[method]table.call\x01\x11\x01@\x03\x04self\x0e\x01c\x0f\
+\x05state\x05\0\x10\x04\0\x17[method]table.call-with\x01\x12\x01i\x0b\x01j\x01\x13\
+\x01s\x01@\x01\x05state\x05\0\x14\x04\0\x10[static]app.load\x01\x15\x01h\x0b\x01\
+j\0\x01s\x01@\x02\x04self\x16\x05state\x05\0\x17\x04\0\x15[method]app.set-state\x01\
+\x18\x01i\x01\x01@\x01\x04self\x16\0\x19\x04\0\x10[method]app.view\x01\x1a\x01@\x01\
+\x05codec\x09\x01\0\x04\0\x09set-codec\x01\x1b\x01p\x07\x01@\x01\x08literals\x1c\
+\x01\0\x04\0\x0cset-literals\x01\x1d\x01o\x02ss\x01p\x1e\x01@\0\0\x1f\x04\0\x13m\
+essage-fingerprint\x01\x20\x01@\0\0\x7f\x04\0\x0ahas-update\x01!\x01k\x05\x01j\x01\
+\"\x01s\x01@\x02\x05state\x05\x07message\x05\0#\x04\0\x06update\x01$\x04\0\x12th\
+awing:core/guest\x05\x0c\x04\0\x14thawing:core/thawing\x04\0\x0b\x0d\x01\0\x07th\
+awing\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.227.\
+1\x10wit-bindgen-rust\x060.41.0";
         };
     };
 }
@@ -1828,48 +2559,54 @@ pub use __export_thawing_impl as export;
 #[unsafe(link_section = "component-type:wit-bindgen:0.41.0:thawing:core:thawing-with-all-of-its-exports-removed:encoded world")]
 #[doc(hidden)]
 #[allow(clippy::octal_escapes)]
-pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 1854] = *b"\
-\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xa0\x0d\x01A\x02\x01\
-A\x0b\x01B\x14\x04\0\x07closure\x03\x01\x04\0\x07element\x03\x01\x01p}\x04\0\x05\
+pub static __WIT_BINDGEN_COMPONENT_TYPE: [u8; 2135] = *b"\
+\0asm\x0d\0\x01\0\0\x19\x16wit-component-encoding\x04\0\x07\xb9\x0f\x01A\x02\x01\
+A\x0c\x01B\x18\x04\0\x07closure\x03\x01\x04\0\x07element\x03\x01\x01p}\x04\0\x05\
 bytes\x03\0\x02\x01r\x01\x06amountv\x04\0\x06pixels\x03\0\x04\x01r\x04\x01rv\x01\
 gv\x01bv\x01av\x04\0\x05color\x03\0\x06\x01r\x04\x03topv\x05rightv\x06bottomv\x04\
 leftv\x04\0\x07padding\x03\0\x08\x01q\x04\x04fill\0\0\x0cfill-portion\x01{\0\x06\
 shrink\0\0\x05fixed\x01v\0\x04\0\x06length\x03\0\x0a\x01m\x03\x04left\x06center\x05\
-right\x04\0\x0ahorizontal\x03\0\x0c\x01i\0\x01@\0\0\x0e\x04\0\x14[constructor]cl\
-osure\x01\x0f\x01h\0\x01@\x01\x04self\x10\0y\x04\0\x12[method]closure.id\x01\x11\
-\x03\0\x12thawing:core/types\x05\0\x02\x03\0\0\x07element\x02\x03\0\0\x07closure\
-\x02\x03\0\0\x06pixels\x02\x03\0\0\x07padding\x02\x03\0\0\x06length\x02\x03\0\0\x0a\
-horizontal\x02\x03\0\0\x05color\x01BK\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\
-\0\x02\x03\x02\x01\x02\x04\0\x07closure\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x06p\
-ixels\x03\0\x04\x02\x03\x02\x01\x04\x04\0\x07padding\x03\0\x06\x02\x03\x02\x01\x05\
-\x04\0\x06length\x03\0\x08\x02\x03\x02\x01\x06\x04\0\x0ahorizontal\x03\0\x0a\x02\
-\x03\x02\x01\x07\x04\0\x05color\x03\0\x0c\x04\0\x06button\x03\x01\x04\0\x08check\
-box\x03\x01\x04\0\x06column\x03\x01\x04\0\x04text\x03\x01\x01i\x01\x01i\x0e\x01@\
-\x01\x07content\x12\0\x13\x04\0\x13[constructor]button\x01\x14\x01h\x0e\x01i\x03\
-\x01@\x02\x04self\x15\x08on-press\x16\0\x13\x04\0\x1c[method]button.on-press-wit\
-h\x01\x17\x01@\x01\x04self\x15\0\x12\x04\0\x1b[method]button.into-element\x01\x18\
-\x01i\x0f\x01@\x02\x05labels\x0ais-checked\x7f\0\x19\x04\0\x15[constructor]check\
-box\x01\x1a\x01h\x0f\x01@\x02\x04self\x1b\x09on-toggle\x16\0\x19\x04\0\x1a[metho\
-d]checkbox.on-toggle\x01\x1c\x01@\x01\x04self\x1b\0\x12\x04\0\x1d[method]checkbo\
-x.into-element\x01\x1d\x01i\x10\x01@\0\0\x1e\x04\0\x13[constructor]column\x01\x1f\
-\x01p\x12\x01@\x01\x08children\x20\0\x1e\x04\0\x17[static]column.from-vec\x01!\x01\
-h\x10\x01@\x02\x04self\"\x06amount\x05\0\x1e\x04\0\x16[method]column.spacing\x01\
-#\x01@\x02\x04self\"\x07padding\x07\0\x1e\x04\0\x16[method]column.padding\x01$\x01\
-@\x02\x04self\"\x05width\x09\0\x1e\x04\0\x14[method]column.width\x01%\x01@\x02\x04\
-self\"\x06height\x09\0\x1e\x04\0\x15[method]column.height\x01&\x01@\x02\x04self\"\
-\x05width\x05\0\x1e\x04\0\x18[method]column.max-width\x01'\x01@\x02\x04self\"\x05\
-align\x0b\0\x1e\x04\0\x16[method]column.align-x\x01(\x01@\x02\x04self\"\x04clip\x7f\
-\0\x1e\x04\0\x13[method]column.clip\x01)\x01@\x02\x04self\"\x05child\x12\0\x1e\x04\
-\0\x13[method]column.push\x01*\x01@\x02\x04self\"\x08children\x20\0\x1e\x04\0\x15\
-[method]column.extend\x01+\x01@\x01\x04self\"\0\x12\x04\0\x1b[method]column.into\
--element\x01,\x01i\x11\x01@\x01\x08fragments\0-\x04\0\x11[constructor]text\x01.\x01\
-h\x11\x01@\x02\x04self/\x04size\x05\0-\x04\0\x11[method]text.size\x010\x01@\x02\x04\
-self/\x05color\x0d\0-\x04\0\x12[method]text.color\x011\x01@\x02\x04self/\x08styl\
-e-fn\x16\0-\x04\0\x12[method]text.style\x012\x01@\x01\x04self/\0\x12\x04\0\x19[m\
-ethod]text.into-element\x013\x03\0\x13thawing:core/widget\x05\x08\x04\04thawing:\
-core/thawing-with-all-of-its-exports-removed\x04\0\x0b-\x01\0'thawing-with-all-o\
-f-its-exports-removed\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-com\
-ponent\x070.227.1\x10wit-bindgen-rust\x060.41.0";
+right\x04\0\x0ahorizontal\x03\0\x0c\x01m\x03\x07bincode\x08postcard\x04json\x04\0\
+\x05codec\x03\0\x0e\x01q\x05\x07integer\x01x\0\x05float\x01u\0\x04text\x01s\0\x09\
+character\x01t\0\x07boolean\x01\x7f\0\x04\0\x07literal\x03\0\x10\x01i\0\x01@\0\0\
+\x12\x04\0\x14[constructor]closure\x01\x13\x01h\0\x01@\x01\x04self\x14\0y\x04\0\x12\
+[method]closure.id\x01\x15\x03\0\x12thawing:core/types\x05\0\x02\x03\0\0\x07elem\
+ent\x02\x03\0\0\x07closure\x02\x03\0\0\x05bytes\x02\x03\0\0\x06pixels\x02\x03\0\0\
+\x07padding\x02\x03\0\0\x06length\x02\x03\0\0\x0ahorizontal\x02\x03\0\0\x05color\
+\x01BV\x02\x03\x02\x01\x01\x04\0\x07element\x03\0\0\x02\x03\x02\x01\x02\x04\0\x07\
+closure\x03\0\x02\x02\x03\x02\x01\x03\x04\0\x05bytes\x03\0\x04\x02\x03\x02\x01\x04\
+\x04\0\x06pixels\x03\0\x06\x02\x03\x02\x01\x05\x04\0\x07padding\x03\0\x08\x02\x03\
+\x02\x01\x06\x04\0\x06length\x03\0\x0a\x02\x03\x02\x01\x07\x04\0\x0ahorizontal\x03\
+\0\x0c\x02\x03\x02\x01\x08\x04\0\x05color\x03\0\x0e\x04\0\x06button\x03\x01\x04\0\
+\x08checkbox\x03\x01\x04\0\x06column\x03\x01\x04\0\x04text\x03\x01\x04\0\x06cust\
+om\x03\x01\x01i\x01\x01i\x10\x01@\x01\x07content\x15\0\x16\x04\0\x13[constructor\
+]button\x01\x17\x01h\x10\x01i\x03\x01@\x02\x04self\x18\x08on-press\x19\0\x16\x04\
+\0\x1c[method]button.on-press-with\x01\x1a\x01@\x01\x04self\x18\0\x15\x04\0\x1b[\
+method]button.into-element\x01\x1b\x01i\x11\x01@\x02\x05labels\x0ais-checked\x7f\
+\0\x1c\x04\0\x15[constructor]checkbox\x01\x1d\x01h\x11\x01@\x02\x04self\x1e\x09o\
+n-toggle\x19\0\x1c\x04\0\x1a[method]checkbox.on-toggle\x01\x1f\x01@\x01\x04self\x1e\
+\0\x15\x04\0\x1d[method]checkbox.into-element\x01\x20\x01i\x12\x01@\0\0!\x04\0\x13\
+[constructor]column\x01\"\x01p\x15\x01@\x01\x08children#\0!\x04\0\x17[static]col\
+umn.from-vec\x01$\x01h\x12\x01@\x02\x04self%\x06amount\x07\0!\x04\0\x16[method]c\
+olumn.spacing\x01&\x01@\x02\x04self%\x07padding\x09\0!\x04\0\x16[method]column.p\
+adding\x01'\x01@\x02\x04self%\x05width\x0b\0!\x04\0\x14[method]column.width\x01(\
+\x01@\x02\x04self%\x06height\x0b\0!\x04\0\x15[method]column.height\x01)\x01@\x02\
+\x04self%\x05width\x07\0!\x04\0\x18[method]column.max-width\x01*\x01@\x02\x04sel\
+f%\x05align\x0d\0!\x04\0\x16[method]column.align-x\x01+\x01@\x02\x04self%\x04cli\
+p\x7f\0!\x04\0\x13[method]column.clip\x01,\x01@\x02\x04self%\x05child\x15\0!\x04\
+\0\x13[method]column.push\x01-\x01@\x02\x04self%\x08children#\0!\x04\0\x15[metho\
+d]column.extend\x01.\x01@\x01\x04self%\0\x15\x04\0\x1b[method]column.into-elemen\
+t\x01/\x01i\x13\x01@\x01\x08fragments\00\x04\0\x11[constructor]text\x011\x01h\x13\
+\x01@\x02\x04self2\x04size\x07\00\x04\0\x11[method]text.size\x013\x01@\x02\x04se\
+lf2\x05color\x0f\00\x04\0\x12[method]text.color\x014\x01@\x02\x04self2\x08style-\
+fn\x19\00\x04\0\x12[method]text.style\x015\x01@\x01\x04self2\0\x15\x04\0\x19[met\
+hod]text.into-element\x016\x01i\x14\x01@\x02\x04names\x05props\x05\07\x04\0\x13[\
+constructor]custom\x018\x01h\x14\x01@\x03\x04self9\x05events\x07closure\x19\07\x04\
+\0\x11[method]custom.on\x01:\x01@\x01\x04self9\0\x15\x04\0\x1b[method]custom.int\
+o-element\x01;\x03\0\x13thawing:core/widget\x05\x09\x04\04thawing:core/thawing-w\
+ith-all-of-its-exports-removed\x04\0\x0b-\x01\0'thawing-with-all-of-its-exports-\
+removed\x03\0\0\0G\x09producers\x01\x0cprocessed-by\x02\x0dwit-component\x070.22\
+7.1\x10wit-bindgen-rust\x060.41.0";
 #[inline(never)]
 #[doc(hidden)]
 pub fn __link_custom_section_describing_imports() {
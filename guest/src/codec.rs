@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+/// One of the codecs, as chosen by the host.
+pub use crate::core::types::Codec as Format;
+
+/// The format of the state, messages and closure arguments exchanged
+/// with the host, which must be the same on both sides.
+pub trait Codec {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, String>;
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String>;
+}
+
+pub struct Bincode;
+
+impl Codec for Bincode {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        bincode::serialize(value).map_err(|error| error.to_string())
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        bincode::deserialize(bytes).map_err(|error| error.to_string())
+    }
+}
+
+pub struct Postcard;
+
+impl Codec for Postcard {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        postcard::to_allocvec(value).map_err(|error| error.to_string())
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        postcard::from_bytes(bytes).map_err(|error| error.to_string())
+    }
+}
+
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        serde_json::to_vec(value).map_err(|error| error.to_string())
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        serde_json::from_slice(bytes).map_err(|error| error.to_string())
+    }
+}
+
+impl Codec for Format {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Bincode => Bincode.encode(value),
+            Self::Postcard => Postcard.encode(value),
+            Self::Json => Json.encode(value),
+        }
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Bincode => Bincode.decode(bytes),
+            Self::Postcard => Postcard.decode(bytes),
+            Self::Json => Json.decode(bytes),
+        }
+    }
+}
+
+static FORMAT: Mutex<Format> = Mutex::new(Format::Json);
+
+pub fn current() -> Format {
+    *FORMAT.lock().unwrap()
+}
+
+pub fn set(format: Format) {
+    *FORMAT.lock().unwrap() = format;
+}
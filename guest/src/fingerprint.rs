@@ -0,0 +1,71 @@
+use std::marker::PhantomData;
+
+/// The fingerprints of the types visited so far, by name.
+pub type Visited = Vec<(&'static str, &'static [(&'static str, &'static str)])>;
+
+/// The structure of a `#[thawing::data]` type, sent to the host to
+/// check it still matches its own.
+pub trait Fingerprint {
+    const NAME: &'static str;
+
+    const FINGERPRINT: &'static [(&'static str, &'static str)];
+
+    fn nested(_visited: &mut Visited) {}
+}
+
+/// The fingerprint of the type followed by the ones of its nested types,
+/// whose entries are prefixed by their name, as the host expects them.
+pub fn flatten<T: Fingerprint>() -> Vec<(String, String)> {
+    let mut visited = vec![(T::NAME, T::FINGERPRINT)];
+    T::nested(&mut visited);
+
+    visited
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, (ty, fingerprint))| {
+            fingerprint.iter().map(move |(name, shape)| {
+                let name = if index == 0 {
+                    name.to_string()
+                } else {
+                    format!("{ty}::{name}")
+                };
+
+                (name, shape.to_string())
+            })
+        })
+        .collect()
+}
+
+#[doc(hidden)]
+pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> Default for Probe<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait Nested {
+    fn visit(&self, visited: &mut Visited);
+}
+
+impl<T: Fingerprint + ?Sized> Nested for &Probe<T> {
+    fn visit(&self, visited: &mut Visited) {
+        if visited.iter().any(|(name, _)| *name == T::NAME) {
+            return;
+        }
+
+        visited.push((T::NAME, T::FINGERPRINT));
+        T::nested(visited);
+    }
+}
+
+#[doc(hidden)]
+pub trait Leaf {
+    fn visit(&self, visited: &mut Visited);
+}
+
+impl<T: ?Sized> Leaf for Probe<T> {
+    fn visit(&self, _visited: &mut Visited) {}
+}
@@ -6,6 +6,7 @@ use std::marker::PhantomData;
 #[macro_export]
 macro_rules! thaw {
     ($app: ident) => {
+        use $crate::codec::Codec as _;
         use $crate::{bindings, runtime};
         use bindings::exports::thawing::core::guest;
 
@@ -22,35 +23,89 @@ macro_rules! thaw {
                 _Table
             }
 
-            fn call(&self, c: guest::Closure) -> guest::Bytes {
+            fn call(&self, c: guest::Closure) -> Result<guest::Bytes, String> {
                 let table = runtime::TABLE.lock().unwrap();
                 let closure = table.get(&c.id()).unwrap();
                 closure.call()
             }
 
-            fn call_with(&self, c: guest::Closure, state: guest::Bytes) -> guest::Bytes {
+            fn call_with(
+                &self,
+                c: guest::Closure,
+                state: guest::Bytes,
+            ) -> Result<guest::Bytes, String> {
                 let table = runtime::TABLE.lock().unwrap();
                 let closure = table.get(&c.id()).unwrap();
                 closure.call_with(state)
             }
         }
 
-        impl guest::GuestApp for $app
+        /// The app, which the host keeps alive across views.
+        #[doc(hidden)]
+        struct _App(std::cell::RefCell<$app>);
+
+        impl guest::GuestApp for _App
         where
-            Self: Application,
+            $app: Application,
         {
-            fn new(state: Vec<u8>) -> Self {
-                $crate::bincode::deserialize(&state).unwrap()
+            fn load(state: guest::Bytes) -> Result<guest::App, String> {
+                $crate::codec::current()
+                    .decode::<$app>(&state)
+                    .map(|app| guest::App::new(_App(std::cell::RefCell::new(app))))
+            }
+
+            fn set_state(&self, state: guest::Bytes) -> Result<(), String> {
+                *self.0.borrow_mut() = $crate::codec::current().decode(&state)?;
+
+                Ok(())
             }
 
             fn view(&self) -> guest::Element {
-               <Self as $crate::Application>::view(self).into().into_raw()
+               <$app as $crate::Application>::view(&self.0.borrow()).into().into_raw()
             }
         }
 
         impl guest::Guest for _Component {
-            type App = $app;
+            type App = _App;
             type Table = _Table;
+
+            fn set_codec(codec: guest::Codec) {
+                $crate::codec::set(codec);
+            }
+
+            fn set_literals(literals: Vec<guest::Literal>) {
+                $crate::literal::set(literals);
+            }
+
+            fn message_fingerprint() -> Vec<(String, String)> {
+                $crate::fingerprint::flatten::<<$app as $crate::Application>::Message>()
+            }
+
+            fn has_update() -> bool {
+                <$app as $crate::Application>::UPDATE
+            }
+
+            fn update(
+                state: guest::Bytes,
+                message: guest::Bytes,
+            ) -> Result<Option<guest::Bytes>, String> {
+                let codec = $crate::codec::current();
+                let mut app: $app = codec
+                    .decode(&state)
+                    .map_err(|error| format!("failed to decode the state: {error}"))?;
+                let message = codec
+                    .decode(&message)
+                    .map_err(|error| format!("failed to decode the message: {error}"))?;
+
+                if !<$app as $crate::Application>::update(&mut app, message) {
+                    return Ok(None);
+                }
+
+                codec
+                    .encode(&app)
+                    .map(Some)
+                    .map_err(|error| format!("failed to encode the state: {error}"))
+            }
         }
 
         bindings::export!(_Component with_types_in bindings);
@@ -58,14 +113,44 @@ macro_rules! thaw {
 }
 
 pub mod thawing {
+    pub use crate::Fingerprint;
     pub use serde;
-    pub use thawing_macro::data;
+    pub use thawing_macro::{data, helper};
+
+    /// The items used by `#[thawing::data]`.
+    #[doc(hidden)]
+    pub mod __private {
+        pub use crate::fingerprint::{Leaf, Nested, Probe, Visited};
+    }
+}
+
+/// The items every generated crate imports, which the imports
+/// of the caller take precedence over.
+pub mod prelude {
+    pub use crate::thawing;
+    pub use crate::widget::{Style, button, checkbox, column, custom, text};
+    pub use crate::{Application, Center, Color, Element, Theme, color};
 }
 
 pub trait Application<Theme = theme::Theme> {
-    fn view(&self) -> impl Into<Element<Theme>>;
+    type Message;
+
+    fn view(&self) -> impl Into<Element<'_, Self::Message, Theme>>;
+
+    /// Whether [`update`](Self::update) is hot reloaded, so the host does not
+    /// encode its state for every message otherwise.
+    const UPDATE: bool = false;
+
+    /// Applies the message, returning `false` if the update logic
+    /// is not hot reloaded, so the host applies it instead.
+    fn update(&mut self, _message: Self::Message) -> bool {
+        false
+    }
 }
 
+pub mod codec;
+pub mod fingerprint;
+pub mod literal;
 pub mod runtime;
 pub mod theme;
 
@@ -74,12 +159,11 @@ mod widgets;
 
 pub mod widget {
     pub use crate::widgets::*;
-    pub fn text<Theme>(content: impl ToString) -> Text<Theme> {
+    pub fn text<'a, Theme>(content: impl ToString) -> Text<'a, Theme> {
         Text::new(content)
     }
 }
 
-pub use bincode;
 pub use bindings::exports::thawing::core::guest;
 pub use bindings::thawing::core;
 pub use core::types::{
@@ -88,23 +172,26 @@ pub use core::types::{
     Length::{self, *},
     Padding, Pixels,
 };
+pub use fingerprint::Fingerprint;
 pub use theme::Theme;
 
-pub struct Element<Theme = theme::Theme> {
+pub struct Element<'a, Message, Theme = theme::Theme> {
     pub(crate) raw: core::types::Element,
+    _message: PhantomData<(&'a (), Message)>,
     _theme: PhantomData<Theme>,
 }
 
-impl<Theme> Element<Theme> {
+impl<'a, Message, Theme> Element<'a, Message, Theme> {
     pub fn into_raw(self) -> core::types::Element {
         self.raw
     }
 }
 
-impl<Theme> From<core::types::Element> for Element<Theme> {
+impl<'a, Message, Theme> From<core::types::Element> for Element<'a, Message, Theme> {
     fn from(raw: core::types::Element) -> Self {
         Element {
             raw,
+            _message: PhantomData,
             _theme: PhantomData,
         }
     }
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex, RwLock};
+
+use crate::core::types::Literal;
+
+static LITERALS: LazyLock<RwLock<Vec<Value>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// The texts patched so far, so each one is only leaked once however many
+/// patches send it.
+static TEXTS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(|| Mutex::new(HashSet::new()));
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Text(&'static str),
+    Character(char),
+    Boolean(bool),
+}
+
+/// Replaces the literals of the view, as sent by the host after a change
+/// that only touched literals.
+pub fn set(literals: Vec<Literal>) {
+    let values = literals
+        .into_iter()
+        .map(|literal| match literal {
+            Literal::Integer(value) => Value::Integer(value),
+            Literal::Float(value) => Value::Float(value),
+            Literal::Text(value) => Value::Text(intern(value)),
+            Literal::Character(value) => Value::Character(value),
+            Literal::Boolean(value) => Value::Boolean(value),
+        })
+        .collect();
+
+    *LITERALS.write().unwrap() = values;
+}
+
+/// Returns the `'static` text equal to `value`, since text literals are
+/// `'static`; the component is dropped on the next reload, along with them.
+fn intern(value: String) -> &'static str {
+    let mut texts = TEXTS.lock().unwrap();
+
+    if let Some(text) = texts.get(value.as_str()) {
+        return text;
+    }
+
+    let text = Box::leak(value.into_boxed_str());
+    let _ = texts.insert(text);
+
+    text
+}
+
+/// Returns the patched value of the literal at `index`, or the one
+/// it was compiled with.
+///
+/// The host replaces every literal of a view with a call to it, whose type
+/// is still inferred from the argument it is given to:
+///
+/// ```
+/// use thawing_guest::literal::patch;
+/// use thawing_guest::column;
+/// use thawing_guest::prelude::{Element, text};
+///
+/// fn view<'a>() -> Element<'a, ()> {
+///     column![text(patch("hello", 0u32)).size(patch(20, 1u32))]
+///         .spacing(patch(20, 2u32))
+///         .padding(patch(20.0, 3u32))
+///         .into()
+/// }
+/// ```
+pub fn patch<T: Patch>(literal: T, index: u32) -> T {
+    LITERALS
+        .read()
+        .unwrap()
+        .get(index as usize)
+        .and_then(|value| T::patch(*value))
+        .unwrap_or(literal)
+}
+
+pub trait Patch: Sized {
+    #[doc(hidden)]
+    fn patch(value: Value) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($ty:ty),*) => {
+        $(
+            impl Patch for $ty {
+                fn patch(value: Value) -> Option<Self> {
+                    match value {
+                        Value::Integer(value) => value.try_into().ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl Patch for f32 {
+    fn patch(value: Value) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(value as f32),
+            _ => None,
+        }
+    }
+}
+
+impl Patch for f64 {
+    fn patch(value: Value) -> Option<Self> {
+        match value {
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Patch for &'static str {
+    fn patch(value: Value) -> Option<Self> {
+        match value {
+            Value::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Patch for char {
+    fn patch(value: Value) -> Option<Self> {
+        match value {
+            Value::Character(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl Patch for bool {
+    fn patch(value: Value) -> Option<Self> {
+        match value {
+            Value::Boolean(value) => Some(value),
+            _ => None,
+        }
+    }
+}
@@ -1,13 +1,14 @@
 use std::collections::HashMap;
 use std::sync::{LazyLock, Mutex};
 
+use crate::codec::{self, Codec};
 use crate::guest::Bytes;
 
 pub static TABLE: LazyLock<Mutex<HashMap<u32, Closure>>> =
     LazyLock::new(|| Mutex::new(HashMap::default()));
 
 pub struct Closure {
-    func: Box<dyn Fn(Bytes) -> Bytes + Send>,
+    func: Box<dyn Fn(Bytes) -> Result<Bytes, String> + Send>,
 }
 
 impl Closure {
@@ -16,9 +17,10 @@ impl Closure {
         S: serde::de::DeserializeOwned + 'static,
         T: serde::Serialize + 'static,
     {
-        let wrapper = move |bytes: Bytes| -> Bytes {
-            let msg = func(bincode::deserialize(&bytes).unwrap());
-            bincode::serialize(&msg).unwrap()
+        let wrapper = move |bytes: Bytes| -> Result<Bytes, String> {
+            let codec = codec::current();
+            let msg = func(decode(codec, &bytes)?);
+            encode(codec, &msg)
         };
 
         Self {
@@ -31,9 +33,10 @@ impl Closure {
         S: serde::de::DeserializeOwned + 'static,
         T: serde::Serialize + 'static,
     {
-        let wrapper = move |bytes: Bytes| -> Bytes {
-            let msg = func(&bincode::deserialize(&bytes).unwrap());
-            bincode::serialize(&msg).unwrap()
+        let wrapper = move |bytes: Bytes| -> Result<Bytes, String> {
+            let codec = codec::current();
+            let msg = func(&decode(codec, &bytes)?);
+            encode(codec, &msg)
         };
 
         Self {
@@ -45,18 +48,31 @@ impl Closure {
     where
         T: serde::Serialize + 'static,
     {
-        let wrapper = move |_state: Bytes| -> Bytes { bincode::serialize(&func()).unwrap() };
+        let wrapper =
+            move |_state: Bytes| -> Result<Bytes, String> { encode(codec::current(), &func()) };
 
         Self {
             func: Box::new(wrapper),
         }
     }
 
-    pub fn call_with(&self, state: Bytes) -> Bytes {
+    pub fn call_with(&self, state: Bytes) -> Result<Bytes, String> {
         (self.func)(state)
     }
 
-    pub fn call(&self) -> Bytes {
+    pub fn call(&self) -> Result<Bytes, String> {
         (self.func)(Vec::new())
     }
 }
+
+fn decode<T: serde::de::DeserializeOwned>(codec: codec::Format, bytes: &[u8]) -> Result<T, String> {
+    codec
+        .decode(bytes)
+        .map_err(|error| format!("failed to decode the argument of a closure: {error}"))
+}
+
+fn encode<T: serde::Serialize>(codec: codec::Format, value: &T) -> Result<Bytes, String> {
+    codec
+        .encode(value)
+        .map_err(|error| format!("failed to encode the result of a closure: {error}"))
+}
@@ -1,4 +1,5 @@
 use crate::Element;
+use crate::codec::{self, Codec};
 use crate::core::types::{Color, Horizontal, Length, Padding, Pixels};
 use crate::core::widget;
 use crate::guest;
@@ -6,20 +7,20 @@ use crate::runtime::{Closure, TABLE};
 
 use std::marker::PhantomData;
 
-pub fn button<Message: serde::Serialize + Clone + Send + 'static, Theme>(
-    content: impl Into<Element<Theme>>,
-) -> Button<Message, Theme> {
+pub fn button<'a, Message: serde::Serialize + Clone + Send + 'static, Theme>(
+    content: impl Into<Element<'a, Message, Theme>>,
+) -> Button<'a, Message, Theme> {
     Button::new(content)
 }
 
-pub struct Button<Message, Theme = crate::Theme> {
+pub struct Button<'a, Message, Theme = crate::Theme> {
     raw: widget::Button,
-    _message: PhantomData<Message>,
+    _message: PhantomData<(&'a (), Message)>,
     _theme: PhantomData<Theme>,
 }
 
-impl<Message: serde::Serialize + Clone + Send + 'static, Theme> Button<Message, Theme> {
-    pub fn new(content: impl Into<Element<Theme>>) -> Self {
+impl<'a, Message: serde::Serialize + Clone + Send + 'static, Theme> Button<'a, Message, Theme> {
+    pub fn new(content: impl Into<Element<'a, Message, Theme>>) -> Self {
         Self {
             raw: widget::Button::new(content.into().into_raw()),
             _message: PhantomData,
@@ -42,20 +43,20 @@ impl<Message: serde::Serialize + Clone + Send + 'static, Theme> Button<Message,
     }
 }
 
-pub fn checkbox<Message: serde::Serialize + 'static, Theme>(
+pub fn checkbox<'a, Message: serde::Serialize + 'static, Theme>(
     label: impl Into<String>,
     is_checked: bool,
-) -> Checkbox<Message, Theme> {
+) -> Checkbox<'a, Message, Theme> {
     Checkbox::new(label, is_checked)
 }
 
-pub struct Checkbox<Message, Theme = crate::Theme> {
+pub struct Checkbox<'a, Message, Theme = crate::Theme> {
     raw: widget::Checkbox,
-    _message: PhantomData<Message>,
+    _message: PhantomData<(&'a (), Message)>,
     _theme: PhantomData<Theme>,
 }
 
-impl<Message: serde::Serialize + 'static, Theme> Checkbox<Message, Theme> {
+impl<'a, Message: serde::Serialize + 'static, Theme> Checkbox<'a, Message, Theme> {
     pub fn new(label: impl Into<String>, is_checked: bool) -> Self {
         Self {
             raw: widget::Checkbox::new(&label.into(), is_checked),
@@ -75,6 +76,59 @@ impl<Message: serde::Serialize + 'static, Theme> Checkbox<Message, Theme> {
     }
 }
 
+pub fn custom<'a, Message: serde::Serialize + Clone + Send + 'static, Theme>(
+    name: impl Into<String>,
+    props: impl serde::Serialize,
+) -> Custom<'a, Message, Theme> {
+    Custom::new(name, props)
+}
+
+pub struct Custom<'a, Message, Theme = crate::Theme> {
+    raw: widget::Custom,
+    _message: PhantomData<(&'a (), Message)>,
+    _theme: PhantomData<Theme>,
+}
+
+impl<'a, Message: serde::Serialize + Clone + Send + 'static, Theme> Custom<'a, Message, Theme> {
+    pub fn new(name: impl Into<String>, props: impl serde::Serialize) -> Self {
+        let props = codec::current().encode(&props).unwrap();
+
+        Self {
+            raw: widget::Custom::new(&name.into(), &props),
+            _message: PhantomData,
+            _theme: PhantomData,
+        }
+    }
+
+    pub fn on_event(self, event: &str, message: Message) -> Self {
+        let closure = guest::Closure::new();
+        TABLE
+            .lock()
+            .unwrap()
+            .insert(closure.id(), Closure::stateless(move || message.clone()));
+
+        self.with_closure(event, closure)
+    }
+
+    pub fn on<T>(self, event: &str, f: impl Fn(T) -> Message + Send + 'static) -> Self
+    where
+        T: serde::de::DeserializeOwned + 'static,
+    {
+        let closure = guest::Closure::new();
+        TABLE
+            .lock()
+            .unwrap()
+            .insert(closure.id(), Closure::stateful(f));
+
+        self.with_closure(event, closure)
+    }
+
+    fn with_closure(mut self, event: &str, closure: guest::Closure) -> Self {
+        self.raw = self.raw.on(event, closure);
+        self
+    }
+}
+
 #[macro_export]
 macro_rules! column {
     () => (
@@ -85,29 +139,39 @@ macro_rules! column {
     );
 }
 
-pub use column;
+pub use crate::column;
 
-pub struct Column<Theme = crate::Theme> {
+/// Creates a [`Column`] with the given children, e.g. built from an iterator.
+pub fn column<'a, Message, Theme>(
+    children: impl IntoIterator<Item = Element<'a, Message, Theme>>,
+) -> Column<'a, Message, Theme> {
+    Column::with_children(children)
+}
+
+pub struct Column<'a, Message, Theme = crate::Theme> {
     raw: widget::Column,
+    _message: PhantomData<(&'a (), Message)>,
     _theme: PhantomData<Theme>,
 }
 
-impl<Theme> Column<Theme> {
+impl<'a, Message, Theme> Column<'a, Message, Theme> {
     pub fn new() -> Self {
         Self {
             raw: widget::Column::new(),
+            _message: PhantomData,
             _theme: PhantomData,
         }
     }
 
-    pub fn from_vec(children: Vec<Element<Theme>>) -> Self {
+    pub fn from_vec(children: Vec<Element<'a, Message, Theme>>) -> Self {
         Self {
             raw: widget::Column::from_vec(children.into_iter().map(Element::into_raw).collect()),
+            _message: PhantomData,
             _theme: PhantomData,
         }
     }
 
-    pub fn with_children(children: impl IntoIterator<Item = Element<Theme>>) -> Self {
+    pub fn with_children(children: impl IntoIterator<Item = Element<'a, Message, Theme>>) -> Self {
         let iterator = children.into_iter();
 
         Self::with_capacity(iterator.size_hint().0).extend(iterator)
@@ -152,16 +216,28 @@ impl<Theme> Column<Theme> {
         self
     }
 
-    pub fn push(mut self, content: impl Into<Element<Theme>>) -> Self {
+    pub fn push(mut self, content: impl Into<Element<'a, Message, Theme>>) -> Self {
         self.raw = self.raw.push(content.into().into_raw());
         self
     }
 
-    pub fn extend(self, children: impl IntoIterator<Item = Element<Theme>>) -> Self {
+    pub fn extend(self, children: impl IntoIterator<Item = Element<'a, Message, Theme>>) -> Self {
         children.into_iter().fold(self, Self::push)
     }
 }
 
+impl<'a, Message, Theme> Default for Column<'a, Message, Theme> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, Message, Theme> FromIterator<Element<'a, Message, Theme>> for Column<'a, Message, Theme> {
+    fn from_iter<T: IntoIterator<Item = Element<'a, Message, Theme>>>(iter: T) -> Self {
+        Self::with_children(iter)
+    }
+}
+
 #[macro_export]
 macro_rules! text {
     ($($arg:tt)*) => {
@@ -171,12 +247,12 @@ macro_rules! text {
 
 pub use text;
 
-pub struct Text<Theme = crate::Theme> {
+pub struct Text<'a, Theme = crate::Theme> {
     raw: widget::Text,
-    _theme: PhantomData<Theme>,
+    _theme: PhantomData<(&'a (), Theme)>,
 }
 
-impl<Theme> Text<Theme> {
+impl<'a, Theme> Text<'a, Theme> {
     pub fn new(fragment: impl ToString) -> Self {
         Self {
             raw: widget::Text::new(&fragment.to_string()),
@@ -195,7 +271,7 @@ impl<Theme> Text<Theme> {
     }
 }
 
-impl<Theme> Text<Theme>
+impl<'a, Theme> Text<'a, Theme>
 where
     Theme: serde::de::DeserializeOwned + 'static,
 {
@@ -215,38 +291,44 @@ pub struct Style {
     pub color: Option<Color>,
 }
 
-impl<Theme> From<&str> for Element<Theme> {
-    fn from(content: &str) -> Element<Theme> {
+impl<'a, Message, Theme> From<&'a str> for Element<'a, Message, Theme> {
+    fn from(content: &'a str) -> Self {
         Text::new(content).into()
     }
 }
 
-impl<T: ToString, Theme> From<T> for Text<Theme> {
-    fn from(content: T) -> Text<Theme> {
+impl<'a, T: ToString, Theme> From<T> for Text<'a, Theme> {
+    fn from(content: T) -> Self {
         Text::new(content)
     }
 }
 
-impl<Theme> From<Text<Theme>> for Element<Theme> {
-    fn from(text: Text<Theme>) -> Self {
+impl<'a, Message, Theme> From<Text<'a, Theme>> for Element<'a, Message, Theme> {
+    fn from(text: Text<'a, Theme>) -> Self {
         Element::from(text.raw.into_element())
     }
 }
 
-impl<Message, Theme> From<Button<Message, Theme>> for Element<Theme> {
-    fn from(button: Button<Message, Theme>) -> Self {
+impl<'a, Message, Theme> From<Button<'a, Message, Theme>> for Element<'a, Message, Theme> {
+    fn from(button: Button<'a, Message, Theme>) -> Self {
         Element::from(button.raw.into_element())
     }
 }
 
-impl<Message, Theme> From<Checkbox<Message, Theme>> for Element<Theme> {
-    fn from(checkbox: Checkbox<Message, Theme>) -> Self {
+impl<'a, Message, Theme> From<Checkbox<'a, Message, Theme>> for Element<'a, Message, Theme> {
+    fn from(checkbox: Checkbox<'a, Message, Theme>) -> Self {
         Element::from(checkbox.raw.into_element())
     }
 }
 
-impl<Theme> From<Column<Theme>> for Element<Theme> {
-    fn from(column: Column<Theme>) -> Self {
+impl<'a, Message, Theme> From<Custom<'a, Message, Theme>> for Element<'a, Message, Theme> {
+    fn from(custom: Custom<'a, Message, Theme>) -> Self {
+        Element::from(custom.raw.into_element())
+    }
+}
+
+impl<'a, Message, Theme> From<Column<'a, Message, Theme>> for Element<'a, Message, Theme> {
+    fn from(column: Column<'a, Message, Theme>) -> Self {
         Element::from(column.raw.into_element())
     }
 }
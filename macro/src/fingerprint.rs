@@ -0,0 +1,160 @@
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::visit::{self, Visit};
+
+/// Implements `Fingerprint` with the fields or variants of the type, in order,
+/// so the host can tell when the guest copy of the type changed.
+///
+/// The types of the fields are probed for their own `Fingerprint`, so nested
+/// `#[thawing::data]` types are checked too.
+pub fn expand(input: &syn::DeriveInput) -> TokenStream {
+    let entries: Vec<(String, String)> = match &input.data {
+        syn::Data::Struct(data) => fields(&data.fields),
+        syn::Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let shape = match &variant.fields {
+                    syn::Fields::Named(_) => format!(
+                        "{{ {} }}",
+                        join(fields(&variant.fields), |(name, ty)| format!(
+                            "{name}: {ty}"
+                        ))
+                    ),
+                    syn::Fields::Unnamed(_) => {
+                        format!("({})", join(fields(&variant.fields), |(_, ty)| ty))
+                    }
+                    syn::Fields::Unit => String::new(),
+                };
+
+                (variant.ident.to_string(), shape)
+            })
+            .collect(),
+        syn::Data::Union(_) => Vec::new(),
+    };
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (names, shapes): (Vec<_>, Vec<_>) = entries.into_iter().unzip();
+    let nested = nested(input);
+
+    let fingerprint = |thawing: TokenStream| {
+        quote! {
+            const NAME: &'static str = #name;
+
+            const FINGERPRINT: &'static [(&'static str, &'static str)] = &[#((#names, #shapes)),*];
+
+            fn nested(visited: &mut #thawing::__private::Visited) {
+                #[allow(unused_imports)]
+                use #thawing::__private::{Leaf as _, Nested as _};
+
+                #((&&#thawing::__private::Probe::<#nested>::default()).visit(visited);)*
+            }
+        }
+    };
+    let guest = fingerprint(quote!(thawing));
+
+    // The host only compares fingerprints when hot reloading
+    let host = cfg!(feature = "hot").then(|| {
+        let host = fingerprint(quote!(::thawing));
+
+        quote! {
+            #[cfg(not(target_arch = "wasm32"))]
+            impl #impl_generics ::thawing::Fingerprint for #ident #ty_generics #where_clause {
+                #host
+            }
+        }
+    });
+
+    quote! {
+        #[cfg(target_arch = "wasm32")]
+        impl #impl_generics thawing::Fingerprint for #ident #ty_generics #where_clause {
+            #guest
+        }
+
+        #host
+    }
+}
+
+/// The types appearing in the fields, which may be `#[thawing::data]` types,
+/// leaving out the ones using the generic parameters of the type.
+fn nested(input: &syn::DeriveInput) -> Vec<syn::Type> {
+    struct Types<'a> {
+        generics: &'a HashSet<String>,
+        types: Vec<syn::Type>,
+        seen: HashSet<String>,
+    }
+
+    impl<'ast> Visit<'ast> for Types<'_> {
+        fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+            let mut idents = HashSet::new();
+            idents_of(&quote!(#node), &mut idents);
+
+            if idents.is_disjoint(self.generics) && self.seen.insert(quote!(#node).to_string()) {
+                self.types.push(syn::Type::Path(node.clone()));
+            }
+
+            visit::visit_type_path(self, node);
+        }
+    }
+
+    let generics = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.to_string())
+        .collect();
+
+    let mut types = Types {
+        generics: &generics,
+        types: Vec::new(),
+        seen: HashSet::new(),
+    };
+
+    match &input.data {
+        syn::Data::Struct(data) => types.visit_fields(&data.fields),
+        syn::Data::Enum(data) => {
+            for variant in &data.variants {
+                types.visit_fields(&variant.fields);
+            }
+        }
+        syn::Data::Union(_) => {}
+    }
+
+    types.types
+}
+
+fn idents_of(tokens: &TokenStream, idents: &mut HashSet<String>) {
+    for tree in tokens.clone() {
+        match tree {
+            proc_macro2::TokenTree::Group(group) => idents_of(&group.stream(), idents),
+            proc_macro2::TokenTree::Ident(ident) => {
+                let _ = idents.insert(ident.to_string());
+            }
+            _ => {}
+        }
+    }
+}
+
+fn fields(fields: &syn::Fields) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let name = field
+                .ident
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| index.to_string());
+            let ty = &field.ty;
+
+            (name, quote!(#ty).to_string())
+        })
+        .collect()
+}
+
+fn join(fields: Vec<(String, String)>, f: impl Fn((String, String)) -> String) -> String {
+    fields.into_iter().map(f).collect::<Vec<_>>().join(", ")
+}
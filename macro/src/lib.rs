@@ -1,17 +1,21 @@
+mod fingerprint;
+mod update;
+mod view;
+
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::DeriveInput;
 use syn::parse_macro_input;
 
-fn parse_attributes(attrs: &Vec<syn::Attribute>) -> proc_macro2::TokenStream {
+fn parse_attributes(input: &DeriveInput) -> proc_macro2::TokenStream {
     let mut derive_serialize = quote! {
         #[cfg_attr(target_arch = "wasm32", derive(thawing::serde::Serialize))]
-        #[cfg_attr(not(target_arch = "wasm32"), derive(::thawing::serde::Serialize))]
+        #[cfg_attr(not(target_arch = "wasm32"), derive(::thawing::__private::Serialize))]
     };
 
     let mut derive_deserialize = quote! {
         #[cfg_attr(target_arch = "wasm32", derive(thawing::serde::Deserialize))]
-        #[cfg_attr(not(target_arch = "wasm32"), derive(::thawing::serde::Deserialize))]
+        #[cfg_attr(not(target_arch = "wasm32"), derive(::thawing::__private::Deserialize))]
     };
 
     let mut serde_crate = quote! {
@@ -19,7 +23,9 @@ fn parse_attributes(attrs: &Vec<syn::Attribute>) -> proc_macro2::TokenStream {
         #[cfg_attr(not(target_arch = "wasm32"), serde(crate = "::thawing::serde"))]
     };
 
-    for attr in attrs {
+    let mut serde_default = proc_macro2::TokenStream::new();
+
+    for attr in &input.attrs {
         if attr.path().is_ident("derive") {
             let _ = attr.parse_nested_meta(|meta| {
                 let segments = meta
@@ -46,6 +52,17 @@ fn parse_attributes(attrs: &Vec<syn::Attribute>) -> proc_macro2::TokenStream {
                     derive_deserialize = proc_macro2::TokenStream::new();
                 }
 
+                // Fields missing from the state of a previous layout take their default
+                if matches!(&input.data, syn::Data::Struct(data) if matches!(data.fields, syn::Fields::Named(_)))
+                    && (meta.path.is_ident("Default")
+                        || segments
+                            .windows(2)
+                            .find(|segments| *segments == ["default", "Default"])
+                            .is_some())
+                {
+                    serde_default = quote! { #[serde(default)] };
+                }
+
                 Ok(())
             });
         }
@@ -59,18 +76,95 @@ fn parse_attributes(attrs: &Vec<syn::Attribute>) -> proc_macro2::TokenStream {
         #derive_serialize
         #derive_deserialize
         #serde_crate
+        #serde_default
     }
 }
 
+/// Derives nothing, standing in for the `serde` derives of `#[thawing::data]`
+/// in the host when hot reloading is disabled.
+#[doc(hidden)]
+#[proc_macro_derive(Passthrough, attributes(serde))]
+pub fn passthrough(_item: TokenStream) -> TokenStream {
+    TokenStream::new()
+}
+
+/// Marks a free function, or an `impl` block of a `#[thawing::data]` type,
+/// as used by the view, so it is copied into the guest component.
+#[proc_macro_attribute]
+pub fn helper(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
+
 #[proc_macro_attribute]
 pub fn data(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let input = parse_macro_input!(item as DeriveInput);
-    let attributes = parse_attributes(&input.attrs);
+    let attributes = parse_attributes(&input);
+    let fingerprint = fingerprint::expand(&input);
 
     let expanded = quote! {
         #attributes
         #input
+
+        #fingerprint
     };
 
     TokenStream::from(expanded)
 }
+
+/// Hot reloads the given view, after checking that it only uses
+/// the widgets and methods the guest provides.
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    let view = parse_macro_input!(input as syn::Expr);
+
+    TokenStream::from(view::expand(view))
+}
+
+/// Hot reloads the whole body of a view method, with `self` rebound to the
+/// `#[thawing::data(state)]` of the guest.
+///
+/// Named apart from [`view!`](macro@view), since a function-like macro and an
+/// attribute cannot share a name. Takes an optional `id = ...` for the widget.
+#[proc_macro_attribute]
+pub fn view_fn(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut id = None;
+    let parser = id_parser("view_fn", &mut id);
+    parse_macro_input!(attr with parser);
+
+    let function = parse_macro_input!(item as syn::ItemFn);
+
+    TokenStream::from(view::expand_fn(id, function))
+}
+
+/// Hot reloads an update method, by running it in the component of the
+/// widget with the given `id = ...`, and taking the state it returns.
+///
+/// The method runs natively until the component is loaded, or if it fails.
+///
+/// It cannot return a value, since a `Task` cannot run in the guest. An update
+/// producing tasks can call such a method for its state changes instead, and
+/// keep building its tasks natively.
+#[proc_macro_attribute]
+pub fn update(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut id = None;
+    let parser = id_parser("update", &mut id);
+    parse_macro_input!(attr with parser);
+
+    let function = parse_macro_input!(item as syn::ItemFn);
+
+    TokenStream::from(update::expand(id, function))
+}
+
+fn id_parser<'a>(
+    attribute: &'static str,
+    id: &'a mut Option<syn::Expr>,
+) -> impl syn::parse::Parser<Output = ()> + 'a {
+    syn::meta::parser(move |meta| {
+        if meta.path.is_ident("id") {
+            *id = Some(meta.value()?.parse::<syn::Expr>()?);
+            Ok(())
+        } else {
+            Err(meta.error(format!("unsupported `{attribute}` argument, expected `id`")))
+        }
+    })
+}
@@ -0,0 +1,73 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+pub fn expand(id: Option<syn::Expr>, function: syn::ItemFn) -> TokenStream {
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    let Some(id) = id else {
+        return syn::Error::new(
+            sig.ident.span(),
+            "`#[thawing::update]` needs the `id = ...` of the widget whose component runs it",
+        )
+        .to_compile_error();
+    };
+
+    let mut inputs = sig.inputs.iter();
+
+    match inputs.next() {
+        Some(syn::FnArg::Receiver(receiver))
+            if receiver.reference.is_some() && receiver.mutability.is_some() => {}
+        _ => {
+            return syn::Error::new(
+                sig.ident.span(),
+                "`#[thawing::update]` must be a method taking `&mut self`",
+            )
+            .to_compile_error();
+        }
+    }
+
+    let message = match (inputs.next(), inputs.next()) {
+        (Some(syn::FnArg::Typed(argument)), None) => match argument.pat.as_ref() {
+            syn::Pat::Ident(pat) => pat.ident.clone(),
+            pat => {
+                return syn::Error::new(pat.span(), "the message must be bound to a name")
+                    .to_compile_error();
+            }
+        },
+        _ => {
+            return syn::Error::new(
+                sig.inputs.span(),
+                "the update must take the message as its only argument",
+            )
+            .to_compile_error();
+        }
+    };
+
+    // The guest can only return the new state
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        return syn::Error::new(
+            ty.span(),
+            "a hot reloaded update cannot return a value, since a `Task` cannot run in the guest; \
+             move the state changes to a `#[thawing::update]` method without a return type, \
+             and call it from the update returning the `Task`",
+        )
+        .to_compile_error();
+    }
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            if ::thawing::update(#id, self, &#message) {
+                return;
+            }
+
+            #block
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+
+/// The widgets the guest provides: their function, their type and
+/// their supported methods.
+const GUEST: &[(&str, &str, &[&str])] = &[
+    ("button", "Button", &["on_press", "on_press_with"]),
+    ("checkbox", "Checkbox", &["on_toggle"]),
+    (
+        "column",
+        "Column",
+        &[
+            "spacing",
+            "padding",
+            "width",
+            "height",
+            "max_width",
+            "align_x",
+            "clip",
+            "push",
+            "extend",
+        ],
+    ),
+    ("custom", "Custom", &["on_event", "on"]),
+    ("text", "Text", &["size", "color", "style"]),
+];
+
+/// The items of the `iced` root the guest provides in its own root, as
+/// translated by the imports of the generated crate.
+const ROOT: &[&str] = &[
+    "Center",
+    "Left",
+    "Right",
+    "Color",
+    "color",
+    "Element",
+    "Theme",
+    "Length",
+    "Fill",
+    "FillPortion",
+    "Shrink",
+    "Padding",
+    "Pixels",
+];
+
+/// The widget functions and macros of `iced`, the ones without a guest
+/// counterpart being rejected.
+const ICED: &[&str] = &[
+    "button",
+    "canvas",
+    "center",
+    "checkbox",
+    "column",
+    "combo_box",
+    "container",
+    "horizontal_rule",
+    "horizontal_space",
+    "hover",
+    "image",
+    "keyed_column",
+    "lazy",
+    "markdown",
+    "mouse_area",
+    "opaque",
+    "pane_grid",
+    "pick_list",
+    "pin",
+    "progress_bar",
+    "qr_code",
+    "radio",
+    "responsive",
+    "rich_text",
+    "row",
+    "rule",
+    "scrollable",
+    "shader",
+    "slider",
+    "space",
+    "stack",
+    "svg",
+    "text",
+    "text_editor",
+    "text_input",
+    "themer",
+    "toggler",
+    "tooltip",
+    "vertical_rule",
+    "vertical_slider",
+    "vertical_space",
+];
+
+pub fn expand(view: syn::Expr) -> TokenStream {
+    let mut validator = Validator { errors: Vec::new() };
+    validator.visit_expr(&view);
+
+    let errors = validator
+        .errors
+        .into_iter()
+        .map(|error| error.to_compile_error());
+
+    quote! {
+        {
+            #(#errors)*
+
+            ::thawing::Thawing::from_view(
+                ::thawing::Element::from(#view),
+                ::core::file!(),
+                ::core::line!(),
+                ::core::column!(),
+            )
+        }
+    }
+}
+
+pub fn expand_fn(id: Option<syn::Expr>, function: syn::ItemFn) -> TokenStream {
+    let syn::ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = function;
+
+    let Some(receiver) = sig.receiver() else {
+        return syn::Error::new(
+            sig.ident.span(),
+            "`#[thawing::view_fn]` must be a method taking `&self`",
+        )
+        .to_compile_error();
+    };
+
+    if receiver.reference.is_none() || receiver.mutability.is_some() {
+        return syn::Error::new(receiver.span(), "the view must take `&self`").to_compile_error();
+    }
+
+    let mut validator = Validator { errors: Vec::new() };
+    validator.visit_block(&block);
+
+    let errors = validator
+        .errors
+        .into_iter()
+        .map(|error| error.to_compile_error());
+
+    // The body is checked against the declared type, unless it is opaque
+    let ty = match &sig.output {
+        syn::ReturnType::Type(_, ty) if !matches!(ty.as_ref(), syn::Type::ImplTrait(_)) => {
+            Some(quote!(: #ty))
+        }
+        _ => None,
+    };
+    let id = id.map(|id| quote!(.id(#id)));
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            #(#errors)*
+
+            let view #ty = #block;
+
+            ::thawing::Thawing::from_view(
+                view,
+                ::core::file!(),
+                ::core::line!(),
+                ::core::column!(),
+            )
+            .state(self)
+            #id
+            .into()
+        }
+    }
+}
+
+/// Checks a view against the widgets and methods the guest provides.
+struct Validator {
+    errors: Vec<syn::Error>,
+}
+
+impl<'ast> Visit<'ast> for Validator {
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        let method = node.method.to_string();
+
+        if let Some((widget, methods)) = widget(&node.receiver)
+            && method != "into"
+            && !methods.contains(&method.as_str())
+        {
+            self.errors.push(syn::Error::new(
+                node.method.span(),
+                format!(
+                    "`{widget}::{method}` is not supported by the guest, \
+                     `{widget}` only supports: {}",
+                    methods.join(", ")
+                ),
+            ));
+        }
+
+        visit::visit_expr_method_call(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        match node.func.as_ref() {
+            syn::Expr::Path(path) => self.unsupported(&path.path, ""),
+            func => self.visit_expr(func),
+        }
+
+        for arg in &node.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        // Bare names may be local variables, so only calls of them are checked
+        if node.path.segments.len() > 1 {
+            self.unsupported(&node.path, "");
+        }
+
+        visit::visit_expr_path(self, node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        self.unsupported(&node.path, "!");
+
+        // Only the children of layout macros are plain expressions
+        if node
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "column")
+            && let Ok(children) =
+                node.parse_body_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+        {
+            for child in &children {
+                self.visit_expr(child);
+            }
+        }
+
+        visit::visit_macro(self, node);
+    }
+}
+
+impl Validator {
+    fn unsupported(&mut self, path: &syn::Path, suffix: &str) {
+        let segments: Vec<_> = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let segments: Vec<_> = segments.iter().map(String::as_str).collect();
+
+        // Paths are resolved like the imports of the generated crate: from the
+        // root of `iced`, or from its `widget` module once imported
+        let error = match segments.as_slice() {
+            [name] => {
+                if !ICED.contains(name) || GUEST.iter().any(|(function, ..)| function == name) {
+                    return;
+                }
+
+                let provided: Vec<_> = GUEST.iter().map(|(function, ..)| *function).collect();
+
+                format!(
+                    "`{name}{suffix}` is not supported by the guest, \
+                     which only provides: {}",
+                    provided.join(", ")
+                )
+            }
+            ["iced", "widget", rest @ ..] | ["widget", rest @ ..] => {
+                let Some(widget) = rest.first() else {
+                    return;
+                };
+
+                if GUEST
+                    .iter()
+                    .any(|(function, ty, _)| function == widget || ty == widget)
+                {
+                    return;
+                }
+
+                let provided: Vec<_> = GUEST.iter().map(|(function, ..)| *function).collect();
+
+                format!(
+                    "`{}{suffix}` is not supported by the guest, \
+                     which only provides: {}",
+                    segments.join("::"),
+                    provided.join(", ")
+                )
+            }
+            ["iced", "alignment", "Horizontal", ..] => return,
+            ["iced", root, ..] if ROOT.contains(root) => return,
+            ["iced", ..] => format!("`{}{suffix}` has no guest counterpart", segments.join("::")),
+            _ => return,
+        };
+
+        self.errors.push(syn::Error::new(path.span(), error));
+    }
+}
+
+/// The guest widget an expression builds, if it can be told from its syntax.
+fn widget(expr: &syn::Expr) -> Option<(&'static str, &'static [&'static str])> {
+    let find = |name: &str, by_type: bool| {
+        GUEST
+            .iter()
+            .find(|(function, ty, _)| {
+                if by_type {
+                    *ty == name
+                } else {
+                    *function == name
+                }
+            })
+            .map(|(_, ty, methods)| (*ty, *methods))
+    };
+
+    match expr {
+        syn::Expr::Call(call) => {
+            let syn::Expr::Path(path) = call.func.as_ref() else {
+                return None;
+            };
+
+            let mut segments = path.path.segments.iter().rev();
+            let name = segments.next()?.ident.to_string();
+
+            // e.g. `column(items.iter().map(...))`, or else
+            // `Column::new()` and `Column::with_children(...)`
+            find(&name, false).or_else(|| find(&segments.next()?.ident.to_string(), true))
+        }
+        syn::Expr::Macro(node) => match node.mac.path.segments.last()?.ident.to_string().as_str() {
+            name @ ("column" | "text") => find(name, false),
+            _ => None,
+        },
+        // Every supported method returns the widget itself
+        syn::Expr::MethodCall(call) => {
+            let (widget, methods) = widget(&call.receiver)?;
+            let method = call.method.to_string();
+
+            methods
+                .contains(&method.as_str())
+                .then_some((widget, methods))
+        }
+        syn::Expr::Paren(paren) => widget(&paren.expr),
+        _ => None,
+    }
+}
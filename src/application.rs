@@ -0,0 +1,100 @@
+#[cfg(all(feature = "hot", debug_assertions))]
+use iced::Task;
+use iced::application::{BootFn, UpdateFn, ViewFn};
+use iced::{Application, Program};
+
+#[cfg(all(feature = "hot", debug_assertions))]
+use crate::{Element, Event, Thawing};
+
+/// The id of the widget hosting the whole application.
+#[cfg(all(feature = "hot", debug_assertions))]
+const ID: &str = "thawing::application";
+
+/// Creates an [`Application`] like `iced::application`, which hot reloads
+/// the functions passed as `view` and `update` in debug builds.
+///
+/// They must be paths to functions of the caller's crate (e.g. `Counter::view`),
+/// taking the `#[thawing::data(state)]` and `#[thawing::data(message)]` types,
+/// and only using what the guest provides. The state lives in the host, so it is
+/// kept across reloads.
+///
+/// Only these two are hot reloaded, and `update` only if it returns nothing,
+/// since a [`Task`] cannot be created by the guest. Everything else runs
+/// natively, and needs a restart to change:
+/// - `boot`, which runs once when the application starts.
+/// - `update`, if it returns a value.
+/// - `theme`, `subscription`, `title` and the rest of the settings of the
+///   returned [`Application`].
+///
+/// In release builds, or without the `hot` feature, this is `iced::application` itself.
+#[cfg(all(feature = "hot", debug_assertions))]
+#[track_caller]
+pub fn application<State, Message>(
+    boot: impl BootFn<State, Message> + 'static,
+    update: impl UpdateFn<State, Message> + 'static,
+    view: impl for<'a> ViewFn<'a, State, Message, iced::Theme, iced::Renderer> + 'static,
+) -> Application<impl Program<State = State, Message = Message, Theme = iced::Theme>>
+where
+    State: serde::Serialize + serde::de::DeserializeOwned + 'static,
+    Message: std::fmt::Debug
+        + Send
+        + serde::Serialize
+        + serde::de::DeserializeOwned
+        + crate::Fingerprint
+        + 'static,
+{
+    let caller = std::panic::Location::caller();
+    let (file, line, column) = (caller.file(), caller.line(), caller.column());
+
+    iced::application(
+        move || {
+            let (state, task) = boot.boot();
+            let thaw = crate::thaw::<Message>(ID).then(|event| {
+                if let Event::Failed(error) = event {
+                    tracing::error!("{error}");
+                }
+
+                Task::none()
+            });
+
+            (state, Task::batch([task, thaw]))
+        },
+        move |state: &mut State, message: Message| {
+            if crate::update(ID, state, &message) {
+                return Task::none();
+            }
+
+            update.update(state, message)
+        },
+        hot(move |state| {
+            Thawing::from_view(view.view(state), file, line, column)
+                .state(state)
+                .id(ID)
+                .into()
+        }),
+    )
+}
+
+/// Creates an [`Application`] with `iced::application`, since nothing is hot
+/// reloaded in release builds or without the `hot` feature.
+#[cfg(not(all(feature = "hot", debug_assertions)))]
+pub fn application<State, Message>(
+    boot: impl BootFn<State, Message> + 'static,
+    update: impl UpdateFn<State, Message> + 'static,
+    view: impl for<'a> ViewFn<'a, State, Message, iced::Theme, iced::Renderer> + 'static,
+) -> Application<impl Program<State = State, Message = Message, Theme = iced::Theme>>
+where
+    State: 'static,
+    Message: std::fmt::Debug + Send + 'static,
+{
+    iced::application(boot, update, view)
+}
+
+/// Makes the view closure generic over the lifetime of the state.
+#[cfg(all(feature = "hot", debug_assertions))]
+fn hot<State, Message, F>(view: F) -> F
+where
+    F: for<'a> Fn(&'a State) -> Element<'a, Message>,
+{
+    view
+}
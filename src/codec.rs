@@ -0,0 +1,117 @@
+#[cfg(feature = "hot")]
+use std::collections::HashMap;
+#[cfg(feature = "hot")]
+use std::sync::{LazyLock, RwLock};
+
+#[cfg(feature = "hot")]
+use crate::widget::Id;
+
+/// The format of the state, messages and closure arguments exchanged
+/// with the guest, which must be the same on both sides.
+pub trait Codec {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, crate::Error>;
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, crate::Error>;
+}
+
+/// Compact, but a change of layout can only be told by a failure to decode.
+#[cfg(feature = "hot")]
+#[derive(Debug, Clone, Copy)]
+pub struct Bincode;
+
+#[cfg(feature = "hot")]
+impl Codec for Bincode {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, crate::Error> {
+        Ok(bincode::serialize(value)?)
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, crate::Error> {
+        Ok(bincode::deserialize(bytes)?)
+    }
+}
+
+/// The most compact, with varint encoded integers.
+#[cfg(feature = "hot")]
+#[derive(Debug, Clone, Copy)]
+pub struct Postcard;
+
+#[cfg(feature = "hot")]
+impl Codec for Postcard {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, crate::Error> {
+        Ok(postcard::to_allocvec(value)?)
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, crate::Error> {
+        Ok(postcard::from_bytes(bytes)?)
+    }
+}
+
+/// Self-describing, so fields can be added to the state across reloads,
+/// and the payloads can be inspected.
+#[derive(Debug, Clone, Copy)]
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, crate::Error> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, crate::Error> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// One of the codecs, chosen with [`Config::codec`](crate::Config::codec).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    Bincode,
+    Postcard,
+    #[default]
+    Json,
+}
+
+impl Codec for Format {
+    fn encode<T: serde::Serialize>(&self, value: &T) -> Result<Vec<u8>, crate::Error> {
+        match self {
+            #[cfg(feature = "hot")]
+            Self::Bincode => Bincode.encode(value),
+            #[cfg(feature = "hot")]
+            Self::Postcard => Postcard.encode(value),
+            // Nothing reaches a guest without hot reloading, so only custom
+            // widgets encode anything, and they never leave the host
+            #[cfg(not(feature = "hot"))]
+            Self::Bincode | Self::Postcard => Json.encode(value),
+            Self::Json => Json.encode(value),
+        }
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, crate::Error> {
+        match self {
+            #[cfg(feature = "hot")]
+            Self::Bincode => Bincode.decode(bytes),
+            #[cfg(feature = "hot")]
+            Self::Postcard => Postcard.decode(bytes),
+            #[cfg(not(feature = "hot"))]
+            Self::Bincode | Self::Postcard => Json.decode(bytes),
+            Self::Json => Json.decode(bytes),
+        }
+    }
+}
+
+/// The formats chosen by [`thaw_with`](crate::thaw_with) for each widget,
+/// which encodes its state before its component is loaded.
+#[cfg(feature = "hot")]
+static FORMATS: LazyLock<RwLock<HashMap<Id, Format>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// The format of the widget with the given id, or the default one.
+#[cfg(feature = "hot")]
+pub(crate) fn of(id: Option<&Id>) -> Format {
+    id.and_then(|id| FORMATS.read().unwrap().get(id).copied())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "hot")]
+pub(crate) fn register(id: &Id, format: Format) {
+    let _ = FORMATS.write().unwrap().insert(id.clone(), format);
+}
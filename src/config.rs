@@ -0,0 +1,178 @@
+use std::path::PathBuf;
+
+use crate::codec::Format;
+
+/// The configuration used to build the guest component.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub(crate) cache_dir: Option<PathBuf>,
+    pub(crate) profile: Profile,
+    pub(crate) cargo_flags: Vec<String>,
+    pub(crate) features: Vec<String>,
+    pub(crate) target: String,
+    pub(crate) artifact: String,
+    pub(crate) guest: Option<Dependency>,
+    pub(crate) history: usize,
+    pub(crate) include: Vec<String>,
+    pub(crate) exclude: Vec<String>,
+    pub(crate) codec: Format,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            cache_dir: None,
+            profile: Profile::Debug,
+            cargo_flags: Vec::new(),
+            features: Vec::new(),
+            target: String::from("wasm32-unknown-unknown"),
+            artifact: String::from("component"),
+            guest: None,
+            history: 5,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            codec: Format::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the directory where the generated crates and their
+    /// precompiled components are kept between runs.
+    ///
+    /// Defaults to `thawing` in the target directory of the caller's crate. The
+    /// directory must not be writable by other users, since the precompiled
+    /// components are loaded from it as native code.
+    pub fn cache_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_dir = Some(path.into());
+        self
+    }
+
+    pub fn profile(mut self, profile: Profile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn cargo_flag(mut self, flag: impl Into<String>) -> Self {
+        self.cargo_flags.push(flag.into());
+        self
+    }
+
+    pub fn cargo_flags(mut self, flags: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.cargo_flags.extend(flags.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    pub fn features(mut self, features: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.features.extend(features.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn target(mut self, target: impl Into<String>) -> Self {
+        self.target = target.into();
+        self
+    }
+
+    /// Sets the package name of the generated crate, which is also
+    /// the name of the resulting `.wasm` artifact.
+    pub fn artifact(mut self, artifact: impl Into<String>) -> Self {
+        self.artifact = artifact.into();
+        self
+    }
+
+    /// Overrides where the generated crate gets `thawing_guest` from.
+    ///
+    /// By default, the `thawing_guest` package of the caller's workspace
+    /// is used, falling back to the one next to this crate. The published
+    /// crate does not ship it, so it must be set when neither exists.
+    pub fn guest(mut self, dependency: Dependency) -> Self {
+        self.guest = Some(dependency);
+        self
+    }
+
+    /// Sets how many previously working components are kept around,
+    /// so they can be restored with [`rollback`](crate::rollback).
+    pub fn history(mut self, history: usize) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Watches the extra files matching the glob, relative to the caller's crate,
+    /// rebuilding whenever any of them changes (e.g. `"assets/**/*"`).
+    pub fn include(mut self, glob: impl Into<String>) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+
+    /// Ignores the files matching the glob, relative to the caller's crate,
+    /// even if the view depends on them.
+    pub fn exclude(mut self, glob: impl Into<String>) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+
+    /// Sets the format of the data exchanged with the guest of the widget.
+    ///
+    /// [`thaw_with`](crate::thaw_with) should be called before the widget is
+    /// first viewed, or its state is encoded again once the guest is loaded.
+    ///
+    /// Defaults to [`Format::Json`], the only one which lets fields be added
+    /// to the state without a restart.
+    pub fn codec(mut self, codec: Format) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    pub(crate) fn build_args(&self) -> Vec<String> {
+        let mut args = match &self.profile {
+            Profile::Debug => vec![],
+            Profile::Release => vec![String::from("--release")],
+            Profile::Custom(profile) => vec![String::from("--profile"), profile.clone()],
+        };
+
+        if !self.features.is_empty() {
+            args.push(String::from("--features"));
+            args.push(self.features.join(","));
+        }
+
+        args.extend(self.cargo_flags.iter().cloned());
+        args
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Profile {
+    Debug,
+    Release,
+    Custom(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dependency {
+    Path(PathBuf),
+    Git { url: String, rev: Option<String> },
+}
+
+impl Dependency {
+    pub(crate) fn to_toml(&self) -> String {
+        match self {
+            Dependency::Path(path) => {
+                format!("{{ path = {:?} }}", path.display().to_string())
+            }
+            Dependency::Git { url, rev: None } => format!("{{ git = {url:?} }}"),
+            Dependency::Git {
+                url,
+                rev: Some(rev),
+            } => format!("{{ git = {url:?}, rev = {rev:?} }}"),
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+use crate::codec::{self, Codec};
+use crate::runtime::{self, Bytes};
+use crate::widget::Filter;
+
+pub type Element = crate::Element<'static, Message>;
+
+type Factory =
+    dyn Fn(&[u8], codec::Format, &Closures) -> Result<Element, crate::Error> + Send + Sync;
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, Arc<Factory>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Registers a custom widget factory, making it available to the guest
+/// through `thawing_guest::widget::custom(name, props)`.
+///
+/// The `props` sent by the guest are deserialized into `Props` with the
+/// codec of its runtime, and the closures attached with `on` are available
+/// through [`Closures`].
+pub fn register<Props, F>(name: impl Into<String>, factory: F)
+where
+    Props: serde::de::DeserializeOwned,
+    F: Fn(Props, &Closures) -> Element + Send + Sync + 'static,
+{
+    let factory = move |props: &[u8], codec: codec::Format, closures: &Closures| {
+        let props = codec.decode(props)?;
+
+        Ok(factory(props, closures))
+    };
+
+    // The registry is only written here, by a single insert, so it is never
+    // left inconsistent by a panic
+    REGISTRY
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.into(), Arc::new(factory));
+}
+
+/// Creates a [`Custom`] widget, built natively by the factory registered
+/// under `name`; the guest counterpart is `thawing_guest::widget::custom`.
+pub fn custom<Message>(name: impl Into<String>, props: impl serde::Serialize) -> Custom<Message> {
+    Custom::new(name, props)
+}
+
+pub struct Custom<Message> {
+    name: String,
+    props: Result<Bytes, crate::Error>,
+    handlers: Vec<(String, Box<dyn Fn(Option<Bytes>) -> Option<Message>>)>,
+}
+
+impl<Message> Custom<Message> {
+    pub fn new(name: impl Into<String>, props: impl serde::Serialize) -> Self {
+        Self {
+            name: name.into(),
+            props: codec::Format::default().encode(&props),
+            handlers: Vec::new(),
+        }
+    }
+
+    pub fn on_event(mut self, event: impl Into<String>, message: Message) -> Self
+    where
+        Message: Clone + 'static,
+    {
+        self.handlers
+            .push((event.into(), Box::new(move |_| Some(message.clone()))));
+        self
+    }
+
+    /// Handles the event with the data the factory gave it, dropping the
+    /// messages whose data is not a `T`.
+    pub fn on<T>(mut self, event: impl Into<String>, f: impl Fn(T) -> Message + 'static) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        let event = event.into();
+        let name = self.name.clone();
+
+        let handler = {
+            let event = event.clone();
+
+            move |data: Option<Bytes>| {
+                let codec = codec::Format::default();
+                // Events without data are `()`, which not every codec encodes as nothing
+                let decoded = match data {
+                    Some(data) => codec.decode(&data),
+                    None => codec.encode(&()).and_then(|data| codec.decode(&data)),
+                };
+
+                match decoded {
+                    Ok(data) => Some(f(data)),
+                    Err(error) => {
+                        tracing::error!("Failed to decode `{event}` of `{name}`: {error}");
+                        None
+                    }
+                }
+            }
+        };
+
+        self.handlers.push((event, Box::new(handler)));
+        self
+    }
+}
+
+impl<Message: 'static> From<Custom<Message>> for crate::Element<'static, Message> {
+    fn from(custom: Custom<Message>) -> Self {
+        let props = match custom.props {
+            Ok(props) => props,
+            Err(error) => return iced_widget::text(error.to_string()).size(12).into(),
+        };
+
+        // Built natively, so the props and data never leave the host
+        let mut builder = Builder::new(custom.name, props, codec::Format::default());
+        let mut handlers = Vec::with_capacity(custom.handlers.len());
+
+        for (closure, (event, handler)) in custom.handlers.into_iter().enumerate() {
+            builder.on(event, closure as u32);
+            handlers.push(handler);
+        }
+
+        match builder.build() {
+            Ok(element) => Filter::new(element, move |message: self::Message| {
+                let handler = handlers.get(message.closure as usize)?;
+
+                match message.data.transpose() {
+                    Ok(data) => handler(data),
+                    Err(error) => {
+                        tracing::error!("Failed to encode the data of a custom widget: {error}");
+                        None
+                    }
+                }
+            }),
+            Err(error) => iced_widget::text(error.to_string()).size(12).into(),
+        }
+    }
+}
+
+/// A message of a custom widget, triggering the closure it was given.
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub(crate) closure: u32,
+    /// The data of the message, whose encoding failure is reported once
+    /// the message is handled.
+    pub(crate) data: Option<Result<Bytes, crate::Error>>,
+}
+
+#[derive(Debug, Default)]
+pub struct Closures {
+    closures: HashMap<String, u32>,
+    codec: codec::Format,
+}
+
+impl Closures {
+    pub fn get(&self, event: &str) -> Option<Closure> {
+        let codec = self.codec;
+
+        self.closures
+            .get(event)
+            .map(|&closure| Closure { closure, codec })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Closure {
+    closure: u32,
+    /// The codec of the runtime the closure belongs to.
+    codec: codec::Format,
+}
+
+impl Closure {
+    pub fn message(&self) -> Message {
+        Message {
+            closure: self.closure,
+            data: None,
+        }
+    }
+
+    /// Triggers the closure with the value, whose encoding failure drops the
+    /// message and is reported once it is handled.
+    pub fn with<T: serde::Serialize>(&self, value: T) -> Message {
+        Message {
+            closure: self.closure,
+            data: Some(self.codec.encode(&value)),
+        }
+    }
+}
+
+pub(crate) struct Builder {
+    name: String,
+    props: Bytes,
+    closures: Closures,
+}
+
+impl Builder {
+    pub fn new(name: String, props: Bytes, codec: codec::Format) -> Self {
+        Self {
+            name,
+            props,
+            closures: Closures {
+                closures: HashMap::new(),
+                codec,
+            },
+        }
+    }
+
+    pub fn on(&mut self, event: String, closure: u32) {
+        self.closures.closures.insert(event, closure);
+    }
+
+    pub fn build(self) -> Result<Element, crate::Error> {
+        let factory = REGISTRY
+            .read()
+            .map_err(runtime::Error::from)?
+            .get(&self.name)
+            .cloned()
+            .ok_or(runtime::Error::CustomWidgetNotFound(self.name))?;
+
+        factory(&self.props, self.closures.codec, &self.closures)
+    }
+}
@@ -1,4 +1,6 @@
+use std::fmt;
 use std::io;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use crate::runtime;
@@ -8,27 +10,56 @@ pub enum Error {
     #[error("io failed with: {0}")]
     IO(Arc<io::Error>),
     #[error("cargo component build failed:\n{0}")]
-    CargoComponent(String),
+    CargoComponent(BuildFailure),
+    #[error("artifact `{0}` not found in cargo output")]
+    ArtifactNotFound(String),
+    #[error(
+        "`thawing_guest` was not found in the workspace of the caller, \
+         set where to get it from with `Config::guest`"
+    )]
+    GuestNotFound,
     #[error("parsing failed with: {0}")]
     Parsing(ParserError),
     #[error("failed to recv on a channel")]
     RecvFailed,
     #[error("failed to send on a channel")]
     SendFailed,
+    #[cfg(feature = "hot")]
     #[error("wasm engine failed: {0}")]
     WasmEngine(Arc<wasmtime::Error>),
     #[error("runtime failed: {0}")]
     Runtime(runtime::Error),
+    #[cfg(feature = "hot")]
     #[error("serialization failed: {0}")]
     Serialization(Arc<bincode::Error>),
+    #[error("json failed with: {0}")]
+    Json(Arc<serde_json::Error>),
+    #[cfg(feature = "hot")]
+    #[error("postcard failed with: {0}")]
+    Postcard(postcard::Error),
 }
 
+#[cfg(feature = "hot")]
 impl From<bincode::Error> for Error {
     fn from(error: bincode::Error) -> Self {
         Self::Serialization(Arc::new(error))
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Json(Arc::new(error))
+    }
+}
+
+#[cfg(feature = "hot")]
+impl From<postcard::Error> for Error {
+    fn from(error: postcard::Error) -> Self {
+        Self::Postcard(error)
+    }
+}
+
+#[cfg(feature = "hot")]
 impl From<wasmtime::Error> for Error {
     fn from(error: wasmtime::Error) -> Self {
         Self::WasmEngine(Arc::new(error))
@@ -41,22 +72,92 @@ impl From<io::Error> for Error {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct BuildFailure {
+    pub diagnostics: Vec<Diagnostic>,
+    pub stderr: String,
+}
+
+impl BuildFailure {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+}
+
+impl fmt::Display for BuildFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.errors().next().is_none() {
+            return f.write_str(&self.stderr);
+        }
+
+        for diagnostic in self.errors() {
+            writeln!(f, "{}", diagnostic.rendered)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A compiler message, located in the original source file whenever
+/// it points to code extracted from it.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub location: Option<Location>,
+    pub rendered: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub file: PathBuf,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file.display(), self.line, self.column)
+    }
+}
+
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ParserError {
+    #[cfg(feature = "hot")]
     #[error("failed to parse file: {0}")]
     Syn(Arc<syn::Error>),
     #[error("failed to find macro: {0}")]
     Macro(MacroError),
+    #[error("{location}: `{path}` is used by the view, but has no counterpart in the guest")]
+    Unsupported { path: String, location: Location },
 }
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum MacroError {
     #[error("`thawing::view` macro is missing")]
     ViewMacroMissing,
+    #[error("`thawing::view` macro of `{0}` is missing")]
+    ViewNotFound(String),
     #[error("`[thawing::data(state)]` attribute macro is missing")]
     StateAttributeMissing,
     #[error("`[thawing::data(message)]` attribute macro is missing")]
     MessageAttributeMissing,
+    #[error(
+        "`[thawing::helper]` is used on an `impl` block of `{0}`, which is not a `[thawing::data]` type"
+    )]
+    HelperTypeUnknown(String),
+    #[error("`{0}` is passed to `thawing::application`, but is not a function of the crate")]
+    ApplicationFunction(String),
 }
 
 impl From<MacroError> for Error {
@@ -71,6 +172,7 @@ impl From<ParserError> for Error {
     }
 }
 
+#[cfg(feature = "hot")]
 impl From<syn::Error> for Error {
     fn from(error: syn::Error) -> Self {
         Self::Parsing(ParserError::Syn(Arc::new(error)))
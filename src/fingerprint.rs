@@ -0,0 +1,216 @@
+use std::marker::PhantomData;
+
+/// The fingerprints of the types visited so far, by name.
+pub type Visited = Vec<(&'static str, &'static [(&'static str, &'static str)])>;
+
+/// The structure of a `#[thawing::data]` type, generated on both sides,
+/// so a guest whose copy of the type changed can be told apart.
+pub trait Fingerprint {
+    /// The name of the type, which prefixes its entries when it is nested.
+    const NAME: &'static str;
+
+    /// The fields of a struct, or the variants of an enum, in order,
+    /// along with their types.
+    const FINGERPRINT: &'static [(&'static str, &'static str)];
+
+    /// Visits the `#[thawing::data]` types of the fields or variants, recursively.
+    fn nested(_visited: &mut Visited) {}
+}
+
+/// The fingerprint of the type followed by the ones of its nested types,
+/// whose entries are prefixed by their name.
+#[cfg(feature = "hot")]
+pub(crate) fn flatten<T: Fingerprint>() -> Vec<(String, String)> {
+    let mut visited = vec![(T::NAME, T::FINGERPRINT)];
+    T::nested(&mut visited);
+
+    visited
+        .into_iter()
+        .enumerate()
+        .flat_map(|(index, (ty, fingerprint))| {
+            fingerprint.iter().map(move |(name, shape)| {
+                let name = if index == 0 {
+                    name.to_string()
+                } else {
+                    format!("{ty}::{name}")
+                };
+
+                (name, shape.to_string())
+            })
+        })
+        .collect()
+}
+
+/// Visits a field type if it is a `#[thawing::data]` type, through autoref
+/// specialization: [`Nested`] takes precedence over [`Leaf`] when it applies.
+#[doc(hidden)]
+pub struct Probe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized> Default for Probe<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait Nested {
+    fn visit(&self, visited: &mut Visited);
+}
+
+impl<T: Fingerprint + ?Sized> Nested for &Probe<T> {
+    fn visit(&self, visited: &mut Visited) {
+        // Recursive types are only visited once
+        if visited.iter().any(|(name, _)| *name == T::NAME) {
+            return;
+        }
+
+        visited.push((T::NAME, T::FINGERPRINT));
+        T::nested(visited);
+    }
+}
+
+#[doc(hidden)]
+pub trait Leaf {
+    fn visit(&self, visited: &mut Visited);
+}
+
+impl<T: ?Sized> Leaf for Probe<T> {
+    fn visit(&self, _visited: &mut Visited) {}
+}
+
+/// Describes how the fingerprint of the guest differs from the one of the host.
+#[cfg(feature = "hot")]
+pub(crate) fn compare(
+    host: &[(String, String)],
+    guest: &[(String, String)],
+) -> Result<(), Vec<String>> {
+    let mut differences = Vec::new();
+
+    for (index, (name, shape)) in host.iter().enumerate() {
+        match guest.iter().position(|(guest, _)| guest == name) {
+            None => differences.push(format!("`{name}` was removed from the guest")),
+            Some(found) if guest[found].1 != *shape => differences.push(format!(
+                "`{name}` is `{shape}` in the host, but `{}` in the guest",
+                guest[found].1
+            )),
+            // The encoding is positional, so reordering breaks it too
+            Some(found) if position(host, index) != position(guest, found) => {
+                differences.push(format!(
+                    "`{name}` moved from position {} to {} in the guest",
+                    position(host, index),
+                    position(guest, found)
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (name, _) in guest {
+        if !host.iter().any(|(host, _)| host == name) {
+            differences.push(format!("`{name}` was added to the guest"));
+        }
+    }
+
+    if differences.is_empty() {
+        Ok(())
+    } else {
+        Err(differences)
+    }
+}
+
+/// The position of the entry among the ones of the same type.
+#[cfg(feature = "hot")]
+fn position(fingerprint: &[(String, String)], index: usize) -> usize {
+    let ty = |name: &str| name.rsplit_once("::").map(|(ty, _)| ty.to_owned());
+    let own = ty(&fingerprint[index].0);
+
+    fingerprint[..index]
+        .iter()
+        .filter(|(name, _)| ty(name) == own)
+        .count()
+}
+
+#[cfg(all(test, feature = "hot"))]
+mod tests {
+    use super::*;
+
+    fn fingerprint(entries: &[(&str, &str)]) -> Vec<(String, String)> {
+        entries
+            .iter()
+            .map(|(name, shape)| (name.to_string(), shape.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_the_same_fingerprint() {
+        let message = fingerprint(&[("Increment", "()"), ("Set", "(i64)")]);
+
+        assert_eq!(compare(&message, &message), Ok(()));
+    }
+
+    #[test]
+    fn describes_every_difference() {
+        let host = fingerprint(&[("Increment", "()"), ("Decrement", "()"), ("Set", "(i64)")]);
+        let guest = fingerprint(&[("Decrement", "()"), ("Set", "(u32)"), ("Reset", "()")]);
+
+        assert_eq!(
+            compare(&host, &guest),
+            Err(vec![
+                String::from("`Increment` was removed from the guest"),
+                String::from("`Decrement` moved from position 1 to 0 in the guest"),
+                String::from("`Set` is `(i64)` in the host, but `(u32)` in the guest"),
+                String::from("`Reset` was added to the guest"),
+            ])
+        );
+    }
+
+    #[test]
+    fn compares_positions_within_each_type() {
+        let host = fingerprint(&[("Edit", "(Field)"), ("Field::name", "String")]);
+        let guest = fingerprint(&[
+            ("Edit", "(Field)"),
+            ("Clear", "()"),
+            ("Field::name", "String"),
+        ]);
+
+        assert_eq!(
+            compare(&host, &guest),
+            Err(vec![String::from("`Clear` was added to the guest")])
+        );
+    }
+
+    #[test]
+    fn flattens_nested_types_once() {
+        struct Node;
+
+        impl Fingerprint for Node {
+            const NAME: &'static str = "Node";
+            const FINGERPRINT: &'static [(&'static str, &'static str)] =
+                &[("value", "u32"), ("children", "Vec<Node>")];
+
+            fn nested(visited: &mut Visited) {
+                (&&Probe::<Node>::default()).visit(visited);
+            }
+        }
+
+        struct Message;
+
+        impl Fingerprint for Message {
+            const NAME: &'static str = "Message";
+            const FINGERPRINT: &'static [(&'static str, &'static str)] = &[("Tree", "(Node)")];
+
+            fn nested(visited: &mut Visited) {
+                (&&Probe::<Node>::default()).visit(visited);
+            }
+        }
+
+        assert_eq!(
+            flatten::<Message>(),
+            fingerprint(&[
+                ("Tree", "(Node)"),
+                ("Node::value", "u32"),
+                ("Node::children", "Vec<Node>"),
+            ])
+        );
+    }
+}
@@ -4,19 +4,17 @@ mod widget;
 use std::collections::HashMap;
 
 use iced_core::{Widget, element};
-use wasmtime::component::{Resource, ResourceTable};
+use wasmtime::component::{Resource, ResourceAny, ResourceTable};
 
 use crate::Element;
+use crate::codec::{self, Codec};
+use crate::custom;
 use crate::runtime::thawing::core;
-use crate::runtime::{self, Bytes, Empty};
+use crate::runtime::{self, Empty};
 
-type Table<T> = HashMap<u32, T>;
+pub use crate::custom::Message;
 
-#[derive(Debug, Clone)]
-pub struct Message {
-    pub(crate) closure: u32,
-    pub(crate) data: Option<Bytes>,
-}
+type Table<T> = HashMap<u32, T>;
 
 impl Message {
     pub fn stateless<U: 'static>(resource: &Resource<U>) -> Self {
@@ -26,21 +24,26 @@ impl Message {
         }
     }
 
-    pub fn stateful<T: serde::Serialize, U: 'static>(resource: &Resource<U>, value: T) -> Self {
-        let bytes = bincode::serialize(&value).unwrap();
-
+    pub fn stateful<T: serde::Serialize, U: 'static>(
+        resource: &Resource<U>,
+        codec: codec::Format,
+        value: T,
+    ) -> Self {
         Self {
             closure: resource.rep(),
-            data: Some(bytes),
+            data: Some(codec.encode(&value)),
         }
     }
 }
 
-#[derive(Default)]
 pub(crate) struct State<'a> {
     pub(crate) table: ResourceTable,
     pub(crate) element: Table<Element<'a, Message>>,
+    pub(crate) custom: Table<custom::Builder>,
     pub(crate) runtime: Option<runtime::State<'a>>,
+    /// The app of the guest, kept alive across views.
+    pub(crate) app: Option<ResourceAny>,
+    pub(crate) codec: codec::Format,
 }
 
 // This should be safe, `wasmtime::Store` seems to require `Send` because of `Preview 3`,
@@ -48,11 +51,14 @@ pub(crate) struct State<'a> {
 unsafe impl<'a> Send for State<'a> {}
 
 impl<'a> State<'a> {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(codec: codec::Format) -> Self {
         Self {
             table: ResourceTable::new(),
             element: Table::new(),
+            custom: Table::new(),
             runtime: None,
+            app: None,
+            codec,
         }
     }
 }
@@ -1,5 +1,7 @@
+use crate::codec::Codec;
 use crate::guest;
 use crate::runtime::thawing::core;
+use crate::{custom, runtime};
 use core::types::{Color, Horizontal, Length, Padding, Pixels};
 
 use wasmtime::component::Resource;
@@ -25,7 +27,8 @@ impl<'a> core::widget::HostCheckbox for guest::State<'a> {
         closure: Resource<core::types::Closure>,
     ) -> Resource<core::widget::Checkbox> {
         let mut widget = self.get_widget::<Checkbox, _>(&checkbox);
-        widget = widget.on_toggle(move |value| guest::Message::stateful(&closure, value));
+        let codec = self.codec;
+        widget = widget.on_toggle(move |value| guest::Message::stateful(&closure, codec, value));
 
         self.insert(checkbox, widget)
     }
@@ -215,6 +218,53 @@ impl<'a> core::widget::HostColumn for guest::State<'a> {
     }
 }
 
+impl<'a> core::widget::HostCustom for guest::State<'a> {
+    fn new(&mut self, name: String, props: runtime::Bytes) -> Resource<core::widget::Custom> {
+        let custom = self.table.push(()).unwrap();
+        self.custom
+            .insert(custom.rep(), custom::Builder::new(name, props, self.codec));
+
+        custom
+    }
+
+    fn on(
+        &mut self,
+        custom: Resource<core::widget::Custom>,
+        event: String,
+        closure: Resource<core::types::Closure>,
+    ) -> Resource<core::widget::Custom> {
+        if let Some(builder) = self.custom.get_mut(&custom.rep()) {
+            builder.on(event, closure.rep());
+        }
+
+        custom
+    }
+
+    fn into_element(
+        &mut self,
+        custom: Resource<core::widget::Custom>,
+    ) -> Resource<core::widget::Element> {
+        let element = self
+            .custom
+            .remove(&custom.rep())
+            .ok_or_else(|| runtime::Error::CustomWidgetNotFound(format!("#{}", custom.rep())))
+            .map_err(crate::Error::from)
+            .and_then(custom::Builder::build)
+            .unwrap_or_else(|error| {
+                tracing::error!("Failed to build custom widget: {error}");
+                iced_widget::text(error.to_string()).size(12).into()
+            });
+
+        let element = self.insert(custom, element);
+        Resource::new_own(element.rep())
+    }
+
+    fn drop(&mut self, custom: Resource<core::widget::Custom>) -> wasmtime::Result<()> {
+        self.custom.remove(&custom.rep());
+        Ok(())
+    }
+}
+
 mod text {
     #[derive(serde::Serialize, serde::Deserialize)]
     struct Color {
@@ -266,8 +316,14 @@ impl<'a> core::widget::HostText for guest::State<'a> {
         let runtime = self.runtime.as_ref().unwrap().clone();
         widget = widget.style(move |theme| {
             runtime
-                .call::<text::Style>(style_fn.rep(), bincode::serialize(theme).unwrap())
-                .into()
+                .codec
+                .encode(theme)
+                .and_then(|theme| runtime.call::<text::Style>(style_fn.rep(), theme))
+                .map(Into::into)
+                .unwrap_or_else(|error| {
+                    tracing::error!("Failed to style text: {error}");
+                    iced_widget::text::Style::default()
+                })
         });
 
         self.insert(text, widget)
@@ -1,21 +1,37 @@
+#[cfg(feature = "application")]
+mod application;
+pub mod codec;
+pub mod config;
+pub mod custom;
 pub mod error;
+mod fingerprint;
+#[cfg(feature = "hot")]
 mod guest;
 mod runtime;
 mod task;
+mod update;
 mod widget;
 
+#[cfg(feature = "application")]
+pub use application::application;
+pub use config::{Config, Dependency, Profile};
 pub use error::Error;
+pub use fingerprint::Fingerprint;
 pub use serde;
-pub use task::thaw;
-pub use thawing_macro::data;
+pub use task::{Event, rollback, thaw, thaw_with};
+pub use thawing_macro::{data, helper, update, view, view_fn};
+pub use update::update;
 pub use widget::Thawing;
 
 pub type Element<'a, Message> =
     iced_core::Element<'a, Message, iced_widget::Theme, iced_widget::Renderer>;
 
-#[macro_export]
-macro_rules! view {
-    ($widget:expr) => {
-        $crate::Thawing::from_view($crate::Element::from($widget), file!())
-    };
+/// The items used by `#[thawing::data]`, whose derives do nothing without the `hot` feature.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::fingerprint::{Leaf, Nested, Probe, Visited};
+    #[cfg(feature = "hot")]
+    pub use serde::{Deserialize, Serialize};
+    #[cfg(not(feature = "hot"))]
+    pub use thawing_macro::{Passthrough as Deserialize, Passthrough as Serialize};
 }
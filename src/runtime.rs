@@ -1,21 +1,48 @@
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex, PoisonError};
-
+use std::sync::PoisonError;
+#[cfg(feature = "hot")]
+use std::{
+    collections::VecDeque,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
+};
+
+#[cfg(feature = "hot")]
 use wasmtime::Store;
+#[cfg(feature = "hot")]
 use wasmtime::component::{Component, Linker, Resource, ResourceAny, ResourceTable};
 
-use crate::Element;
-use crate::guest;
+#[cfg(feature = "hot")]
+use crate::codec::{self, Codec};
+#[cfg(feature = "hot")]
+use crate::{Element, fingerprint, guest};
 
 pub type Empty = ();
 pub type Bytes = Vec<u8>;
+#[cfg(feature = "hot")]
+pub type Literal = thawing::core::types::Literal;
 
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum Error {
     #[error("root element not found")]
     RootElementNotFound,
+    #[error("runtime is not loaded")]
+    NotLoaded,
+    #[error("no `Thawing` widget with the given id is in the view")]
+    WidgetNotFound,
     #[error("mutex poisoned")]
     MutexPoisoned,
+    #[error("custom widget `{0}` is not registered")]
+    CustomWidgetNotFound(String),
+    #[error("no component found {0} reload(s) back")]
+    HistoryNotFound(usize),
+    #[error("state layout changed, restart required: {0}")]
+    StateLayoutChanged(String),
+    #[error("message layout changed, restart required:\n{0}")]
+    MessageChanged(String),
+    #[error("component failed: {0}")]
+    Guest(String),
 }
 
 impl<T> From<PoisonError<T>> for Error {
@@ -24,6 +51,7 @@ impl<T> From<PoisonError<T>> for Error {
     }
 }
 
+#[cfg(feature = "hot")]
 wasmtime::component::bindgen!({
     world: "thawing",
     with: {
@@ -31,54 +59,155 @@ wasmtime::component::bindgen!({
         "thawing:core/widget/text": Empty,
         "thawing:core/widget/button": Empty,
         "thawing:core/widget/checkbox": Empty,
+        "thawing:core/widget/custom": Empty,
         "thawing:core/types/closure": Empty,
         "thawing:core/types/element": Empty,
     },
 });
 
+#[cfg(feature = "hot")]
+static ENGINE: LazyLock<wasmtime::Engine> = LazyLock::new(wasmtime::Engine::default);
+
+#[cfg(feature = "hot")]
+pub(crate) fn compatibility_hash(hasher: &mut impl Hasher) {
+    ENGINE.precompile_compatibility_hash().hash(hasher);
+}
+
+#[cfg(feature = "hot")]
+#[derive(Debug, Clone)]
+pub(crate) enum Artifact {
+    Wasm {
+        path: PathBuf,
+        precompiled: Option<PathBuf>,
+    },
+    Precompiled(PathBuf),
+}
+
+#[cfg(feature = "hot")]
+impl Artifact {
+    fn load(&self, engine: &wasmtime::Engine) -> Result<Component, crate::Error> {
+        match self {
+            Artifact::Precompiled(path) => {
+                // SAFETY: precompiled artifacts are only read from a directory private to the
+                // user, where they are written whole by `Component::serialize` below. They are
+                // keyed by the engine compatibility hash, the lockfile, the guest and the WIT,
+                // so a stale one is never loaded.
+                Ok(unsafe { Component::deserialize_file(engine, path)? })
+            }
+            Artifact::Wasm { path, precompiled } => {
+                let component = Component::from_file(engine, path)?;
+
+                if let Some(precompiled) = precompiled
+                    && let Err(error) = precompile(&component, precompiled)
+                {
+                    tracing::warn!("Failed to cache precompiled component: {error}");
+                }
+
+                Ok(component)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "hot")]
+fn precompile(component: &Component, path: &Path) -> Result<(), crate::Error> {
+    let bytes = component.serialize()?;
+
+    // Written aside and renamed, so a partial file is never loaded
+    let partial = path.with_extension("cwasm.partial");
+    fs::write(&partial, bytes)?;
+    fs::rename(&partial, path)?;
+
+    Ok(())
+}
+
+#[cfg(feature = "hot")]
 pub(crate) struct Engine<'a> {
     engine: wasmtime::Engine,
     linker: Arc<Linker<guest::State<'a>>>,
-    binary_path: PathBuf,
+    /// The fingerprint of the host `Message`, which every guest must match.
+    message: Arc<[(String, String)]>,
+    codec: codec::Format,
 }
 
+#[cfg(feature = "hot")]
 impl<'a> Clone for Engine<'a> {
     fn clone(&self) -> Self {
         Self {
             engine: self.engine.clone(),
             linker: Arc::clone(&self.linker),
-            binary_path: self.binary_path.clone(),
+            message: Arc::clone(&self.message),
+            codec: self.codec,
         }
     }
 }
 
+#[cfg(feature = "hot")]
 pub(crate) struct Runtime<'a> {
     engine: Engine<'a>,
     state: State<'a>,
+    /// The states replaced by previous reloads, most recent first.
+    history: VecDeque<State<'a>>,
+    capacity: usize,
 }
 
+#[cfg(feature = "hot")]
 impl<'a> Runtime<'a> {
     pub fn engine(&self) -> Engine<'a> {
         self.engine.clone()
     }
 
     pub fn reload(&mut self, state: Result<State<'a>, crate::Error>) -> Result<(), crate::Error> {
-        self.state = state?;
-        self.state.fill_store()?;
+        // Filled before replacing the current state, so it is kept on failure
+        let mut state = state?;
+        state.fill_store()?;
+
+        let previous = std::mem::replace(&mut self.state, state);
+        self.history.push_front(previous);
+        self.history.truncate(self.capacity);
+
+        Ok(())
+    }
+
+    /// Undoes the last reload, discarding the current state.
+    pub fn revert(&mut self) -> Result<(), crate::Error> {
+        self.history
+            .front_mut()
+            .ok_or(Error::HistoryNotFound(1))?
+            .fill_store()?;
+
+        self.state = self.history.pop_front().expect("history is not empty");
+
+        Ok(())
+    }
+
+    /// Restores the state replaced `steps` reloads ago, keeping the current
+    /// one in the history so it can be restored in turn.
+    pub fn rollback(&mut self, steps: usize) -> Result<(), crate::Error> {
+        let index = steps
+            .checked_sub(1)
+            .filter(|index| *index < self.history.len())
+            .ok_or(Error::HistoryNotFound(steps))?;
+
+        self.history[index].fill_store()?;
+
+        let state = self.history.remove(index).expect("index is in bounds");
+        let current = std::mem::replace(&mut self.state, state);
+        self.history.push_front(current);
 
         Ok(())
     }
 }
 
+#[cfg(feature = "hot")]
 impl<'a> Runtime<'a> {
-    pub fn new(manifest: &PathBuf) -> Result<Self, crate::Error> {
-        let binary_path = manifest
-            .join("target")
-            .join("wasm32-unknown-unknown")
-            .join("debug")
-            .join("component.wasm");
-
-        let engine = wasmtime::Engine::default();
+    pub fn new(
+        artifact: &Artifact,
+        capacity: usize,
+        message: Vec<(String, String)>,
+        codec: codec::Format,
+    ) -> Result<Self, crate::Error> {
+        let engine = ENGINE.clone();
         let mut linker = Linker::new(&engine);
         Thawing::add_to_linker(&mut linker, |state| state)?;
 
@@ -86,12 +215,36 @@ impl<'a> Runtime<'a> {
         let engine = Engine {
             engine,
             linker,
-            binary_path,
+            message: message.into(),
+            codec,
         };
-        let mut state = State::new(&engine)?;
+        let mut state = State::new(&engine, artifact)?;
+        state.fill_store()?;
+
+        Ok(Self {
+            engine,
+            state,
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+        })
+    }
+
+    /// Instantiates the artifact with the engine of another runtime, which
+    /// it can then be reloaded into.
+    pub fn with_engine(
+        engine: &Engine<'a>,
+        artifact: &Artifact,
+        capacity: usize,
+    ) -> Result<Self, crate::Error> {
+        let mut state = State::new(engine, artifact)?;
         state.fill_store()?;
 
-        Ok(Self { engine, state })
+        Ok(Self {
+            engine: engine.clone(),
+            state,
+            history: VecDeque::with_capacity(capacity),
+            capacity,
+        })
     }
 
     pub fn view(&self, bytes: &Vec<u8>) -> Result<Element<'a, guest::Message>, crate::Error> {
@@ -101,27 +254,70 @@ impl<'a> Runtime<'a> {
     pub(crate) fn state(&self) -> State<'a> {
         self.state.clone()
     }
+
+    pub(crate) fn current(&self) -> &State<'a> {
+        &self.state
+    }
+
+    pub(crate) fn into_state(self) -> State<'a> {
+        self.state
+    }
+
+    pub fn patch(&self, literals: &[Literal]) -> Result<(), crate::Error> {
+        self.state.set_literals(literals)
+    }
 }
 
+#[cfg(feature = "hot")]
 pub(crate) struct State<'a> {
     pub(crate) store: Arc<Mutex<Store<guest::State<'a>>>>,
     pub(crate) bindings: Arc<Thawing>,
     pub(crate) table: Arc<ResourceAny>,
+    pub(crate) codec: codec::Format,
+    /// Whether the component hot reloads the update logic too.
+    pub(crate) has_update: bool,
 }
 
+#[cfg(feature = "hot")]
 impl<'a> State<'a> {
     pub fn new(
         Engine {
             engine,
             linker,
-            binary_path,
+            message,
+            codec,
         }: &Engine<'a>,
+        artifact: &Artifact,
     ) -> Result<Self, crate::Error> {
-        let component = Component::from_file(&engine, binary_path)?;
+        let component = artifact.load(engine)?;
 
-        let mut store = Store::new(&engine, guest::State::new());
+        let mut store = Store::new(&engine, guest::State::new(*codec));
         let bindings = Thawing::instantiate(&mut store, &component, linker)?;
 
+        let format = match codec {
+            codec::Format::Bincode => thawing::core::types::Codec::Bincode,
+            codec::Format::Postcard => thawing::core::types::Codec::Postcard,
+            codec::Format::Json => thawing::core::types::Codec::Json,
+        };
+        bindings
+            .thawing_core_guest()
+            .call_set_codec(&mut store, format)?;
+
+        let has_update = bindings.thawing_core_guest().call_has_update(&mut store)?;
+
+        let fingerprint = bindings
+            .thawing_core_guest()
+            .call_message_fingerprint(&mut store)?;
+        fingerprint::compare(message, &fingerprint).map_err(|differences| {
+            Error::MessageChanged(
+                differences
+                    .iter()
+                    .map(|difference| format!("- {difference}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            )
+        })?;
+
         let table = bindings
             .thawing_core_guest()
             .table()
@@ -135,6 +331,8 @@ impl<'a> State<'a> {
             store,
             bindings,
             table,
+            codec: *codec,
+            has_update,
         })
     }
 
@@ -142,38 +340,64 @@ impl<'a> State<'a> {
         &self,
         closure: u32,
         data: impl Into<Option<Bytes>>,
-    ) -> Message {
+    ) -> Result<Message, crate::Error> {
         let bytes = match data.into() {
-            Some(bytes) => self.call_stateful(closure, bytes),
-            None => self.call_stateless(closure),
+            Some(bytes) => self.call_stateful(closure, bytes)?,
+            None => self.call_stateless(closure)?,
         };
 
-        bincode::deserialize(&bytes).unwrap()
+        self.codec.decode(&bytes)
+    }
+
+    fn call_stateless(&self, closure: u32) -> Result<Vec<u8>, crate::Error> {
+        let mut store = self.store.lock().map_err(Error::from)?;
+
+        let bytes = self.bindings.thawing_core_guest().table().call_call(
+            &mut *store,
+            *self.table,
+            Resource::new_own(closure),
+        )?;
+
+        Ok(bytes.map_err(Error::Guest)?)
+    }
+
+    fn call_stateful(&self, closure: u32, bytes: Bytes) -> Result<Vec<u8>, crate::Error> {
+        let mut store = self.store.lock().map_err(Error::from)?;
+
+        let bytes = self.bindings.thawing_core_guest().table().call_call_with(
+            &mut *store,
+            *self.table,
+            Resource::new_own(closure),
+            &bytes,
+        )?;
+
+        Ok(bytes.map_err(Error::Guest)?)
     }
 
-    fn call_stateless(&self, closure: u32) -> Vec<u8> {
-        self.bindings
+    fn set_literals(&self, literals: &[Literal]) -> Result<(), crate::Error> {
+        let mut store = self.store.lock().map_err(Error::from)?;
+
+        Ok(self
+            .bindings
             .thawing_core_guest()
-            .table()
-            .call_call(
-                &mut *self.store.lock().unwrap(),
-                *self.table,
-                Resource::new_own(closure),
-            )
-            .unwrap()
+            .call_set_literals(&mut *store, literals)?)
     }
 
-    fn call_stateful(&self, closure: u32, bytes: Bytes) -> Vec<u8> {
-        self.bindings
+    /// Runs the update logic of the component, if any, returning the new state.
+    pub(crate) fn update(
+        &self,
+        state: &Bytes,
+        message: &Bytes,
+    ) -> Result<Option<Bytes>, crate::Error> {
+        let mut store = self.store.lock().map_err(Error::from)?;
+
+        let updated = self
+            .bindings
             .thawing_core_guest()
-            .table()
-            .call_call_with(
-                &mut *self.store.lock().unwrap(),
-                *self.table,
-                Resource::new_own(closure),
-                &bytes,
-            )
-            .unwrap()
+            .call_update(&mut *store, state, message)?
+            .map_err(Error::Guest)?;
+
+        Ok(updated)
     }
 
     fn fill_store(&mut self) -> Result<(), crate::Error> {
@@ -184,24 +408,37 @@ impl<'a> State<'a> {
     }
 }
 
+#[cfg(feature = "hot")]
 impl<'a> State<'a> {
     fn view(&self, bytes: &Vec<u8>) -> Result<Element<'a, guest::Message>, crate::Error> {
-        let mut store = self.store.lock().unwrap();
+        let mut store = self.store.lock().map_err(Error::from)?;
 
         store.data_mut().element.clear();
+        store.data_mut().custom.clear();
         store.data_mut().table = ResourceTable::new();
 
-        let app = self
-            .bindings
-            .thawing_core_guest()
-            .app()
-            .call_constructor(&mut *store, bytes)?;
+        let guest = self.bindings.thawing_core_guest().app();
+
+        // The app is kept alive, so a new state only needs to be decoded
+        let app = match store.data().app {
+            Some(app) => {
+                guest
+                    .call_set_state(&mut *store, app, bytes)?
+                    .map_err(Error::StateLayoutChanged)?;
+
+                app
+            }
+            None => {
+                let app = guest
+                    .call_load(&mut *store, bytes)?
+                    .map_err(Error::StateLayoutChanged)?;
+                store.data_mut().app = Some(app);
+
+                app
+            }
+        };
 
-        let view = self
-            .bindings
-            .thawing_core_guest()
-            .app()
-            .call_view(&mut *store, app)?;
+        let view = guest.call_view(&mut *store, app)?;
 
         let element = store
             .data_mut()
@@ -213,12 +450,15 @@ impl<'a> State<'a> {
     }
 }
 
+#[cfg(feature = "hot")]
 impl<'a> Clone for State<'a> {
     fn clone(&self) -> Self {
         Self {
             store: self.store.clone(),
             bindings: self.bindings.clone(),
             table: self.table.clone(),
+            codec: self.codec,
+            has_update: self.has_update,
         }
     }
 }
@@ -1,47 +1,293 @@
+#[cfg(feature = "hot")]
 mod component;
+#[cfg(feature = "hot")]
 pub(crate) mod executor;
+#[cfg(feature = "hot")]
 mod file;
+#[cfg(feature = "hot")]
+mod generation;
+#[cfg(feature = "hot")]
+mod import;
+#[cfg(feature = "hot")]
+mod literal;
+#[cfg(feature = "hot")]
+mod module;
+#[cfg(feature = "hot")]
+mod process;
+#[cfg(feature = "hot")]
+mod span;
+
+#[cfg(feature = "hot")]
+use std::path::Path;
+use std::time::Duration;
+#[cfg(feature = "hot")]
+use std::time::Instant;
 
 use iced_widget::runtime::Task;
+#[cfg(feature = "hot")]
+use iced_widget::runtime::futures::futures::stream;
 
+use crate::Config;
+#[cfg(feature = "hot")]
+use crate::Fingerprint;
+#[cfg(feature = "hot")]
+use crate::codec;
+#[cfg(feature = "hot")]
+use crate::runtime;
+#[cfg(feature = "hot")]
+use crate::task::generation::{Counter, Generation};
+#[cfg(feature = "hot")]
+use crate::task::module::Dependencies;
 use crate::widget;
 
-pub fn thaw<Message: serde::de::DeserializeOwned + Send + 'static>(
+#[cfg(feature = "hot")]
+pub(crate) use file::{Caller, retry};
+
+/// The progress of a hot reload, as produced by [`thaw`].
+#[derive(Debug, Clone)]
+pub enum Event {
+    ParseStarted,
+    /// The guest code did not change since the last reload, so nothing was built.
+    Skipped,
+    BuildStarted,
+    /// A crate of the guest component finished compiling.
+    BuildProgress(String),
+    BuildFinished(Duration),
+    Instantiated(Duration),
+    /// The guest view was reloaded, with the time since the change was noticed.
+    Reloaded(Duration),
+    /// Only literals of the view changed, and were patched into the running
+    /// component without a build.
+    Patched(Duration),
+    Failed(crate::Error),
+}
+
+#[cfg(feature = "hot")]
+pub fn thaw<Message: serde::de::DeserializeOwned + Fingerprint + Send + 'static>(
+    id: impl Into<widget::Id>,
+) -> Task<Event> {
+    thaw_with::<Message>(id, Config::default())
+}
+
+#[cfg(feature = "hot")]
+pub fn thaw_with<Message: serde::de::DeserializeOwned + Fingerprint + Send + 'static>(
     id: impl Into<widget::Id>,
-) -> Task<()> {
+    config: Config,
+) -> Task<Event> {
     let id = id.into();
+    codec::register(&id, config.codec);
 
-    component::fetch_caller_path::<Message>(&id).then(move |target| {
+    let counter = Counter::of(&id);
+
+    component::fetch_caller::<Message>(&id).then(move |caller| {
         let id = id.clone();
-        let caller = target.clone();
-
-        file::init_directory()
-            .then(move |manifest| file::parse_and_write(&target, manifest))
-            .then(component::build)
-            .then(move |manifest| {
-                let idx = id.clone();
-                let caller = caller.clone();
-
-                component::create_runtime(manifest.clone())
-                    .then(move |runtime| component::set_runtime::<Message>(&idx, runtime))
-                    .then({
-                        let id = id.clone();
-                        move |_| {
+        let config = config.clone();
+        let counter = counter.clone();
+
+        // A new widget has not reloaded anything yet, so it must not skip its first build
+        file::forget(&id);
+
+        file::init_directory(&config, &caller).then(move |generated| {
+            let initial = {
+                let id = id.clone();
+                let history = config.history;
+                let codec = config.codec;
+
+                move |artifact, _generation, timer| {
+                    component::instantiate::<Message>(&id, artifact, history, codec, timer)
+                }
+            };
+
+            let reload = {
+                let id = id.clone();
+                let history = config.history;
+                let codec = config.codec;
+
+                move |artifact, generation, timer| {
+                    component::reload::<Message>(
+                        id.clone(),
+                        artifact,
+                        generation,
+                        timer,
+                        history,
+                        codec,
+                    )
+                }
+            };
+
+            let watch = {
+                let id = id.clone();
+                let caller = caller.path.clone();
+                let generated = generated.clone();
+                let config = config.clone();
+                let counter = counter.clone();
+                let changes = stream::select(
+                    file::watch(Dependencies::new(&caller, &config), counter.clone()),
+                    file::retries(id.clone()),
+                );
+
+                Task::stream(changes).then(move |_| {
+                    let generation = counter.current();
+
+                    compile::<Message, _>(
+                        &id,
+                        &caller,
+                        generated.clone(),
+                        &config,
+                        generation,
+                        reload.clone(),
+                    )
+                })
+            };
+
+            let generation = counter.current();
+
+            compile::<Message, _>(&id, &caller.path, generated, &config, generation, initial)
+                .chain(watch)
+        })
+    })
+}
+
+/// Restores the component of the widget with the given id that was
+/// replaced `steps` reloads ago, up to [`Config::history`].
+#[cfg(feature = "hot")]
+pub fn rollback<Message: Send + 'static>(
+    id: impl Into<widget::Id>,
+    steps: usize,
+) -> Task<Result<(), crate::Error>> {
+    let id = id.into();
+
+    component::rollback::<Message>(id.clone(), steps).map(move |result| {
+        // The next change must be built, even back to the source rolled back from
+        if result.is_ok() {
+            file::forget(&id);
+        }
+
+        result
+    })
+}
+
+#[cfg(feature = "hot")]
+fn compile<Message, F>(
+    id: &widget::Id,
+    caller: &Path,
+    generated: Result<file::Generated, crate::Error>,
+    config: &Config,
+    generation: Generation,
+    load: F,
+) -> Task<Event>
+where
+    Message: Send + 'static,
+    F: Fn(Result<runtime::Artifact, crate::Error>, Generation, Instant) -> Task<Event>
+        + Clone
+        + Send
+        + 'static,
+{
+    let timer = Instant::now();
+    let id = id.clone();
+    let config = config.clone();
+    let latest = generation.clone();
+    let widget = id.clone();
+
+    Task::done(Event::ParseStarted)
+        .chain(
+            file::parse_and_write(caller, generated, &config).then(move |source| {
+                let load = load.clone();
+                let generation = generation.clone();
+
+                if generation.is_stale() {
+                    return Task::none();
+                }
+
+                let source = match source {
+                    Ok(source) => source,
+                    Err(error) => return load(Err(error), generation, timer),
+                };
+
+                if source.is_unchanged(&id) {
+                    tracing::info!("Skipping build, the guest code is unchanged");
+                    return Task::done(Event::Skipped);
+                }
+
+                if source.is_patchable(&id) {
+                    tracing::info!("Only literals changed, patching them in");
+
+                    let id = id.clone();
+
+                    return component::patch::<Message>(&id, source.literals.clone(), timer).map(
+                        move |event| {
+                            if let Event::Patched(_) = event {
+                                source.reloaded(&id);
+                            }
+
+                            event
+                        },
+                    );
+                }
+
+                let build = component::build(&config, source.clone(), generation.clone());
+                let id = id.clone();
+
+                Task::done(Event::BuildStarted).chain(build.then(move |build| {
+                    if generation.is_stale() {
+                        return Task::none();
+                    }
+
+                    match build {
+                        Ok(component::Build::Progress(name)) => {
+                            Task::done(Event::BuildProgress(name))
+                        }
+                        Ok(component::Build::Finished { artifact, duration }) => {
+                            let source = source.clone();
                             let id = id.clone();
-                            let caller = caller.clone();
-                            let manifest = manifest.clone();
-
-                            Task::stream(file::watch(caller.clone())).then(move |_| {
-                                let id = id.clone();
-
-                                file::parse_and_write(&caller, manifest.clone())
-                                    .then(component::build)
-                                    .then(move |manifest| {
-                                        component::reload::<Message>(id.clone(), manifest.err())
-                                    })
-                            })
+
+                            Task::done(Event::BuildFinished(duration)).chain(
+                                load(Ok(artifact), generation.clone(), timer).map(move |event| {
+                                    if let Event::Reloaded(_) = event {
+                                        source.reloaded(&id);
+                                    }
+
+                                    event
+                                }),
+                            )
                         }
-                    })
-            })
-    })
+                        Err(error) => load(Err(error), generation.clone(), timer),
+                    }
+                }))
+            }),
+        )
+        .then(move |event| {
+            // Whatever failed, the component may no longer match the last source
+            if let Event::Failed(_) = event {
+                file::forget(&widget);
+            }
+
+            // A newer change superseded this one, so its outcome is irrelevant
+            if latest.is_stale() {
+                Task::none()
+            } else {
+                Task::done(event)
+            }
+        })
+}
+
+/// Does nothing, since hot reloading needs the `hot` feature.
+#[cfg(not(feature = "hot"))]
+pub fn thaw<Message>(id: impl Into<widget::Id>) -> Task<Event> {
+    thaw_with::<Message>(id, Config::default())
+}
+
+/// Does nothing, since hot reloading needs the `hot` feature.
+#[cfg(not(feature = "hot"))]
+pub fn thaw_with<Message>(_id: impl Into<widget::Id>, _config: Config) -> Task<Event> {
+    Task::none()
+}
+
+/// Does nothing, since hot reloading needs the `hot` feature.
+#[cfg(not(feature = "hot"))]
+pub fn rollback<Message>(
+    _id: impl Into<widget::Id>,
+    _steps: usize,
+) -> Task<Result<(), crate::Error>> {
+    Task::none()
 }
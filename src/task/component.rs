@@ -1,24 +1,37 @@
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, BufReader, Read};
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::time::{Duration, Instant};
 
 use iced_core::Rectangle;
 use iced_core::widget::Operation;
 use iced_core::widget::operation;
+use iced_widget::runtime::futures::futures::SinkExt;
+use iced_widget::runtime::futures::futures::executor::block_on;
 use iced_widget::runtime::{Task, task};
 
-use crate::runtime;
-use crate::task::executor;
-use crate::widget::{Error, Id, State};
-
-pub fn fetch_caller_path<Message: Send + 'static>(id: &Id) -> Task<PathBuf> {
-    struct GetPath<Message> {
+use crate::error::{BuildFailure, Diagnostic, Severity};
+use crate::fingerprint::{self, Fingerprint};
+use crate::task::file::{Caller, Source, create_private_dir};
+use crate::task::generation::Generation;
+use crate::task::process::Group;
+use crate::task::span::SpanMap;
+use crate::task::{Event, executor};
+use crate::widget::{Id, State};
+use crate::{Config, Dependency};
+use crate::{codec, runtime};
+
+pub fn fetch_caller<Message: Send + 'static>(id: &Id) -> Task<Caller> {
+    struct GetCaller<Message> {
         id: iced_core::widget::Id,
-        path: Option<PathBuf>,
+        caller: Option<Caller>,
         message: PhantomData<Message>,
     }
 
-    impl<Message: Send + 'static> Operation<PathBuf> for GetPath<Message> {
+    impl<Message: Send + 'static> Operation<Caller> for GetCaller<Message> {
         fn custom(
             &mut self,
             id: Option<&iced_core::widget::Id>,
@@ -30,7 +43,7 @@ pub fn fetch_caller_path<Message: Send + 'static>(id: &Id) -> Task<PathBuf> {
                     if let Some(State::Loading { caller, .. }) =
                         state.downcast_mut::<State<Message>>()
                     {
-                        self.path = Some(caller.clone());
+                        self.caller = Some(caller.clone());
                         return;
                     }
                 }
@@ -42,77 +55,352 @@ pub fn fetch_caller_path<Message: Send + 'static>(id: &Id) -> Task<PathBuf> {
             &mut self,
             _id: Option<&iced_core::widget::Id>,
             _bounds: Rectangle,
-            operate_on_children: &mut dyn FnMut(&mut dyn Operation<PathBuf>),
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Caller>),
         ) {
             operate_on_children(self)
         }
 
-        fn finish(&self) -> operation::Outcome<PathBuf> {
-            self.path
+        fn finish(&self) -> operation::Outcome<Caller> {
+            self.caller
                 .clone()
                 .map(operation::Outcome::Some)
                 .unwrap_or(operation::Outcome::None)
         }
     }
 
-    task::widget(GetPath {
+    task::widget(GetCaller {
         id: id.0.clone(),
-        path: None,
+        caller: None,
         message: PhantomData::<Message>,
     })
 }
 
-pub fn build(manifest: Result<PathBuf, crate::Error>) -> Task<Result<PathBuf, crate::Error>> {
-    let manifest = match manifest {
-        Err(error) => return Task::done(Err(error)),
-        Ok(manifest) => manifest.to_path_buf(),
-    };
+pub enum Build {
+    Progress(String),
+    Finished {
+        artifact: runtime::Artifact,
+        duration: Duration,
+    },
+}
+
+pub fn build(
+    config: &Config,
+    source: Source,
+    generation: Generation,
+) -> Task<Result<Build, crate::Error>> {
+    let Source {
+        manifest,
+        guest,
+        spans,
+        ..
+    } = source;
+
+    let config = config.clone();
 
     executor::try_spawn_blocking(move |mut sender| {
-        let timer = std::time::Instant::now();
-        let output = std::process::Command::new("cargo")
-            .args([
-                "component",
-                "build",
-                "--target",
-                "wasm32-unknown-unknown",
-                "--target-dir",
-                "target",
-            ])
-            .current_dir(&manifest)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::piped())
-            .spawn()?
-            .wait_with_output()?;
+        let timer = Instant::now();
+
+        let precompiled = precompiled_path(&manifest, &guest, &config)?;
+        if let Some(precompiled) = precompiled.filter(|precompiled| precompiled.exists()) {
+            tracing::info!("Skipping build, found precompiled component at {precompiled:?}");
+            let _ = block_on(sender.send(Build::Finished {
+                artifact: runtime::Artifact::Precompiled(precompiled),
+                duration: timer.elapsed(),
+            }));
+
+            return Ok(());
+        }
+
+        let mut child = Group::spawn(
+            std::process::Command::new("cargo")
+                .args([
+                    "component",
+                    "build",
+                    "--message-format=json",
+                    "--target",
+                    &config.target,
+                    "--target-dir",
+                    "target",
+                ])
+                .args(config.build_args())
+                .current_dir(&manifest)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+        )?;
+
+        let stderr = child.child().stderr.take().map(|mut stderr| {
+            std::thread::spawn(move || {
+                let mut output = String::new();
+                let _ = stderr.read_to_string(&mut output);
+                output
+            })
+        });
+
+        let stdout = child.child().stdout.take().map(|stdout| {
+            let name = config.artifact.replace('-', "_");
+            let manifest = manifest.clone();
+            let mut sender = sender.clone();
+
+            std::thread::spawn(move || {
+                let mut path = None;
+                let mut diagnostics = Vec::new();
+
+                for line in BufReader::new(stdout).lines() {
+                    match CargoMessage::parse(&line?) {
+                        Some(CargoMessage::CompilerArtifact { target, filenames }) => {
+                            if target.name == name {
+                                path = filenames
+                                    .into_iter()
+                                    .find(|file| file.extension().is_some_and(|ext| ext == "wasm"));
+                            }
+
+                            let _ = block_on(sender.send(Build::Progress(target.name)));
+                        }
+                        Some(CargoMessage::CompilerMessage { target, message }) => {
+                            if target.name == name {
+                                diagnostics.push(message.into_diagnostic(&manifest, &spans));
+                            }
+                        }
+                        Some(CargoMessage::Other) | None => {}
+                    }
+                }
+
+                Ok::<_, crate::Error>((path, diagnostics))
+            })
+        });
+
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            if generation.is_stale() {
+                tracing::info!("Cancelling stale `cargo component` build");
+                // `cargo` does not stop its `rustc`s when killed, so kill the whole group
+                child.kill();
+
+                return Ok(());
+            }
+
+            std::thread::sleep(Duration::from_millis(50));
+        };
+
+        let (path, diagnostics) = stdout
+            .and_then(|stdout| stdout.join().ok())
+            .transpose()?
+            .unwrap_or_default();
+        let stderr = stderr
+            .and_then(|stderr| stderr.join().ok())
+            .unwrap_or_default();
 
         tracing::info!(
             "`cargo component` finished with {:?} in {:?}",
-            output.status,
+            status,
             timer.elapsed()
         );
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(crate::Error::CargoComponent(stderr.to_string()));
+        if !status.success() {
+            return Err(crate::Error::CargoComponent(BuildFailure {
+                diagnostics,
+                stderr,
+            }));
         }
 
-        let _ = sender.try_send(manifest);
+        let path = path.ok_or_else(|| crate::Error::ArtifactNotFound(config.artifact.clone()))?;
+        // The build may have resolved the dependencies anew, changing the lockfile
+        let precompiled = precompiled_path(&manifest, &guest, &config)?;
+        let _ = block_on(sender.send(Build::Finished {
+            artifact: runtime::Artifact::Wasm { path, precompiled },
+            duration: timer.elapsed(),
+        }));
 
         Ok(())
     })
 }
 
-pub fn create_runtime(
-    manifest: Result<PathBuf, crate::Error>,
-) -> Task<Result<runtime::Runtime<'static>, crate::Error>> {
+/// The path of the precompiled component, or `None` if it cannot be cached,
+/// since its guest follows a git branch that may move under it.
+fn precompiled_path(
+    manifest: &Path,
+    guest: &Dependency,
+    config: &Config,
+) -> Result<Option<PathBuf>, crate::Error> {
+    if let Dependency::Git { rev: None, .. } = guest {
+        return Ok(None);
+    }
+
+    let mut hasher = DefaultHasher::new();
+    fs::read(manifest.join("Cargo.toml"))?.hash(&mut hasher);
+    // Missing before the first build
+    fs::read(manifest.join("Cargo.lock")).ok().hash(&mut hasher);
+    fs::read(manifest.join("src").join("lib.rs"))?.hash(&mut hasher);
+    config.target.hash(&mut hasher);
+    config.build_args().hash(&mut hasher);
+    runtime::compatibility_hash(&mut hasher);
+
+    // A pinned git guest is covered by the `Cargo.toml`, but a local one can change under it
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    include_str!("../../wit/thawing.wit").hash(&mut hasher);
+    if let Dependency::Path(guest) = guest {
+        hash_sources(guest, &mut hasher)?;
+    }
+
+    let directory = manifest.join("precompiled");
+    create_private_dir(&directory)?;
+
+    let path = directory.join(format!("{:016x}.cwasm", hasher.finish()));
+
+    Ok(Some(path))
+}
+
+/// Hashes the manifest and the sources of a crate, in a stable order.
+pub fn hash_sources(root: &Path, hasher: &mut impl Hasher) -> Result<(), crate::Error> {
+    fs::read(root.join("Cargo.toml"))?.hash(hasher);
+
+    let mut directories = vec![root.join("src")];
+    let mut files = Vec::new();
+
+    while let Some(directory) = directories.pop() {
+        for entry in fs::read_dir(directory)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                directories.push(path);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    for file in files {
+        file.hash(hasher);
+        fs::read(&file)?.hash(hasher);
+    }
+
+    Ok(())
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+enum CargoMessage {
+    CompilerArtifact {
+        target: CargoTarget,
+        filenames: Vec<PathBuf>,
+    },
+    CompilerMessage {
+        target: CargoTarget,
+        message: CompilerMessage,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(serde::Deserialize)]
+struct CargoTarget {
+    name: String,
+}
+
+#[derive(serde::Deserialize)]
+struct CompilerMessage {
+    message: String,
+    level: String,
+    spans: Vec<CompilerSpan>,
+    rendered: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct CompilerSpan {
+    file_name: PathBuf,
+    line_start: usize,
+    column_start: usize,
+    is_primary: bool,
+}
+
+impl CompilerMessage {
+    fn into_diagnostic(self, manifest: &Path, spans: &SpanMap) -> Diagnostic {
+        let severity = match self.level.as_str() {
+            "error" | "error: internal compiler error" => Severity::Error,
+            "warning" => Severity::Warning,
+            "help" => Severity::Help,
+            _ => Severity::Note,
+        };
+
+        let primary = self
+            .spans
+            .iter()
+            .find(|span| span.is_primary && span.file_name == Path::new("src/lib.rs"));
+
+        let location = primary.and_then(|span| spans.locate(span.line_start, span.column_start));
+        let rendered = match &location {
+            Some(location) => spans.render(severity, &self.message, location),
+            None => {
+                let rendered = self.rendered.unwrap_or_else(|| self.message.clone());
+
+                match primary {
+                    Some(span) => rendered.replace(
+                        "src/lib.rs",
+                        &manifest.join(&span.file_name).display().to_string(),
+                    ),
+                    None => rendered,
+                }
+            }
+        };
+
+        Diagnostic {
+            severity,
+            message: self.message,
+            location,
+            rendered,
+        }
+    }
+}
+
+impl CargoMessage {
+    /// Parses a line of `cargo`, which may also come from a build script
+    /// or a procedural macro printing to its stdout.
+    fn parse(line: &str) -> Option<Self> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        match serde_json::from_str(line) {
+            Ok(message) => Some(message),
+            Err(error) => {
+                tracing::debug!("Ignoring unknown `cargo` output {line:?}: {error}");
+                None
+            }
+        }
+    }
+}
+
+/// The result of an operation on a widget, failing if it was not found,
+/// so every build ends with an event.
+fn outcome(
+    result: &Option<Result<(), crate::Error>>,
+) -> operation::Outcome<Result<(), crate::Error>> {
+    operation::Outcome::Some(
+        result
+            .clone()
+            .unwrap_or(Err(crate::Error::Runtime(runtime::Error::WidgetNotFound))),
+    )
+}
+
+pub fn create_runtime<Message: Fingerprint>(
+    artifact: Result<runtime::Artifact, crate::Error>,
+    history: usize,
+    codec: codec::Format,
+) -> Task<Result<(runtime::Runtime<'static>, Duration), crate::Error>> {
     executor::try_spawn_blocking(move |mut sender| {
-        let timer = std::time::Instant::now();
-        let runtime = manifest
-            .as_ref()
-            .map(runtime::Runtime::new)
-            .map_err(|err| err.clone())??;
-        let _ = sender.try_send(runtime);
+        let timer = Instant::now();
+        let runtime = runtime::Runtime::new(
+            &artifact?,
+            history,
+            fingerprint::flatten::<Message>(),
+            codec,
+        )?;
+        let _ = sender.try_send((runtime, timer.elapsed()));
         tracing::info!("Building `runtime::State` took {:?}", timer.elapsed());
 
         Ok(())
@@ -122,14 +410,17 @@ pub fn create_runtime(
 pub fn set_runtime<Message: serde::de::DeserializeOwned + Send + 'static>(
     id: &Id,
     runtime: Result<runtime::Runtime<'static>, crate::Error>,
-) -> Task<()> {
+) -> Task<Result<(), crate::Error>> {
     struct SetRuntime<Message> {
         id: Id,
         runtime: Result<Option<runtime::Runtime<'static>>, crate::Error>,
+        result: Option<Result<(), crate::Error>>,
         message: PhantomData<Message>,
     }
 
-    impl<Message: serde::de::DeserializeOwned + Send + 'static> Operation<()> for SetRuntime<Message> {
+    impl<Message: serde::de::DeserializeOwned + Send + 'static> Operation<Result<(), crate::Error>>
+        for SetRuntime<Message>
+    {
         fn custom(
             &mut self,
             id: Option<&iced_core::widget::Id>,
@@ -139,14 +430,21 @@ pub fn set_runtime<Message: serde::de::DeserializeOwned + Send + 'static>(
             match id {
                 Some(id) if id == &self.id.0 => {
                     if let Some(state) = state.downcast_mut::<State<Message>>() {
-                        let State::Loading { bytes, .. } = state else {
+                        // A failed first build is replaced too, so it can recover
+                        if matches!(state, State::Loaded(Ok(_))) {
                             return;
-                        };
+                        }
 
-                        *state = match self.runtime.as_mut().map(Option::take).map(Option::unwrap) {
-                            Ok(runtime) => State::loaded(runtime, bytes),
-                            Err(error) => State::failed(&error),
-                        };
+                        let result =
+                            match self.runtime.as_mut().map(Option::take).map(Option::unwrap) {
+                                Ok(runtime) => state.load(runtime, &self.id),
+                                Err(error) => {
+                                    *state = State::failed(&error);
+                                    Err(error.clone())
+                                }
+                            };
+
+                        self.result = Some(result);
                     }
                     return;
                 }
@@ -158,24 +456,56 @@ pub fn set_runtime<Message: serde::de::DeserializeOwned + Send + 'static>(
             &mut self,
             _id: Option<&iced_core::widget::Id>,
             _bounds: Rectangle,
-            operate_on_children: &mut dyn FnMut(&mut dyn Operation<()>),
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Result<(), crate::Error>>),
         ) {
             operate_on_children(self)
         }
 
-        fn finish(&self) -> operation::Outcome<()> {
-            operation::Outcome::Some(())
+        fn finish(&self) -> operation::Outcome<Result<(), crate::Error>> {
+            outcome(&self.result)
         }
     }
 
     task::widget(SetRuntime {
         id: id.clone(),
         runtime: runtime.map(Some),
+        result: None,
         message: PhantomData::<Message>,
     })
 }
 
-pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::Error>) -> Task<()> {
+/// Loads the artifact into a new runtime for the widget, replacing its
+/// loading or failed state.
+pub fn instantiate<Message: serde::de::DeserializeOwned + Fingerprint + Send + 'static>(
+    id: &Id,
+    artifact: Result<runtime::Artifact, crate::Error>,
+    history: usize,
+    codec: codec::Format,
+    timer: Instant,
+) -> Task<Event> {
+    let id = id.clone();
+
+    create_runtime::<Message>(artifact, history, codec).then(move |runtime| match runtime {
+        Ok((runtime, duration)) => Task::done(Event::Instantiated(duration)).chain(
+            set_runtime::<Message>(&id, Ok(runtime)).map(move |result| match result {
+                Ok(()) => Event::Reloaded(timer.elapsed()),
+                Err(error) => Event::Failed(error),
+            }),
+        ),
+        Err(error) => set_runtime::<Message>(&id, Err(error.clone()))
+            .then(|_| Task::none())
+            .chain(Task::done(Event::Failed(error))),
+    })
+}
+
+pub fn reload<Message: serde::de::DeserializeOwned + Fingerprint + Send + 'static>(
+    id: impl Into<Id>,
+    artifact: Result<runtime::Artifact, crate::Error>,
+    generation: Generation,
+    timer: Instant,
+    history: usize,
+    codec: codec::Format,
+) -> Task<Event> {
     let id = id.into();
 
     struct FetchEngine<Message> {
@@ -185,7 +515,7 @@ pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::E
         message: PhantomData<Message>,
     }
 
-    impl<Message: Send + 'static> Operation<runtime::Engine<'static>> for FetchEngine<Message> {
+    impl<Message: Send + 'static> Operation<Option<runtime::Engine<'static>>> for FetchEngine<Message> {
         fn custom(
             &mut self,
             id: Option<&iced_core::widget::Id>,
@@ -195,14 +525,12 @@ pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::E
             match id {
                 Some(id) if id == &self.id => {
                     if let Some(state) = state.downcast_mut::<State<Message>>() {
-                        state.error(self.error.clone().map(Error::new));
+                        state.error(self.error.clone());
                         if self.error.is_some() {
                             return;
                         }
 
-                        if let State::Loaded(Ok(inner)) = state {
-                            self.engine = Some(inner.engine());
-                        }
+                        self.engine = state.engine();
                     }
 
                     return;
@@ -215,27 +543,29 @@ pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::E
             &mut self,
             _id: Option<&iced_core::widget::Id>,
             _bounds: Rectangle,
-            operate_on_children: &mut dyn FnMut(&mut dyn Operation<runtime::Engine<'static>>),
+            operate_on_children: &mut dyn FnMut(
+                &mut dyn Operation<Option<runtime::Engine<'static>>>,
+            ),
         ) {
             operate_on_children(self)
         }
 
-        fn finish(&self) -> operation::Outcome<runtime::Engine<'static>> {
-            self.engine
-                .as_ref()
-                .map(runtime::Engine::clone)
-                .map(operation::Outcome::Some)
-                .unwrap_or(operation::Outcome::None)
+        fn finish(&self) -> operation::Outcome<Option<runtime::Engine<'static>>> {
+            operation::Outcome::Some(self.engine.clone())
         }
     }
 
     struct Reload<Message> {
-        id: iced_core::widget::Id,
-        state: Result<runtime::State<'static>, crate::Error>,
+        id: Id,
+        runtime: Option<Result<runtime::Runtime<'static>, crate::Error>>,
+        generation: u64,
+        result: Option<Result<(), crate::Error>>,
         message: PhantomData<Message>,
     }
 
-    impl<Message: Send + 'static> Operation for Reload<Message> {
+    impl<Message: serde::de::DeserializeOwned + Send + 'static> Operation<Result<(), crate::Error>>
+        for Reload<Message>
+    {
         fn custom(
             &mut self,
             id: Option<&iced_core::widget::Id>,
@@ -243,10 +573,12 @@ pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::E
             state: &mut dyn std::any::Any,
         ) {
             match id {
-                Some(id) if id == &self.id => {
-                    if let Some(state) = state.downcast_mut::<State<Message>>() {
-                        state.error(self.state.as_ref().err().cloned().map(Error::new));
-                        state.reload(self.state.clone());
+                Some(id) if id == &self.id.0 => {
+                    if let Some(state) = state.downcast_mut::<State<Message>>()
+                        && let Some(runtime) = self.runtime.take()
+                    {
+                        state.error(runtime.as_ref().err().cloned());
+                        self.result = Some(state.reload(runtime, &self.id, self.generation));
                     }
 
                     return;
@@ -259,35 +591,185 @@ pub fn reload<Message: Send + 'static>(id: impl Into<Id>, error: Option<crate::E
             &mut self,
             _id: Option<&iced_core::widget::Id>,
             _bounds: Rectangle,
-            operate_on_children: &mut dyn FnMut(&mut dyn Operation<()>),
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Result<(), crate::Error>>),
         ) {
             operate_on_children(self)
         }
 
-        fn finish(&self) -> operation::Outcome<()> {
-            operation::Outcome::Some(())
+        fn finish(&self) -> operation::Outcome<Result<(), crate::Error>> {
+            outcome(&self.result)
         }
     }
 
+    let artifact = match artifact {
+        Ok(artifact) => artifact,
+        Err(error) => {
+            return task::widget(FetchEngine {
+                id: id.into(),
+                engine: None,
+                error: Some(error.clone()),
+                message: PhantomData::<Message>,
+            })
+            .then(|_| Task::none())
+            .chain(Task::done(Event::Failed(error)));
+        }
+    };
+
     task::widget(FetchEngine {
         id: id.clone().into(),
         engine: None,
-        error,
+        error: None,
         message: PhantomData::<Message>,
     })
-    .then(|engine| {
+    .then(move |engine| {
+        let artifact = artifact.clone();
+
         executor::try_spawn_blocking(move |mut sender| {
-            let engine = runtime::State::new(&engine)?;
-            let _ = sender.try_send(engine);
+            let timer = Instant::now();
+
+            // The first build failed, or never reached the widget, so there is
+            // no engine to reuse and a whole runtime is instantiated instead
+            let runtime = match &engine {
+                Some(engine) => runtime::Runtime::with_engine(engine, &artifact, history)?,
+                None => runtime::Runtime::new(
+                    &artifact,
+                    history,
+                    fingerprint::flatten::<Message>(),
+                    codec,
+                )?,
+            };
+            let _ = sender.try_send((runtime, timer.elapsed()));
 
             Ok(())
         })
     })
-    .then(move |state| {
-        task::widget(Reload {
-            id: id.clone().into(),
-            state,
-            message: PhantomData::<Message>,
-        })
+    .then(move |runtime| {
+        let (runtime, instantiated) = match runtime {
+            Ok((runtime, duration)) => (Ok(runtime), Task::done(Event::Instantiated(duration))),
+            Err(error) => (Err(error), Task::none()),
+        };
+
+        instantiated.chain(
+            task::widget(Reload {
+                id: id.clone(),
+                runtime: Some(runtime),
+                generation: generation.value(),
+                result: None,
+                message: PhantomData::<Message>,
+            })
+            .map(move |result| match result {
+                Ok(()) => Event::Reloaded(timer.elapsed()),
+                Err(error) => Event::Failed(error),
+            }),
+        )
+    })
+}
+
+pub fn rollback<Message: Send + 'static>(
+    id: impl Into<Id>,
+    steps: usize,
+) -> Task<Result<(), crate::Error>> {
+    struct Rollback<Message> {
+        id: iced_core::widget::Id,
+        steps: usize,
+        result: Option<Result<(), crate::Error>>,
+        message: PhantomData<Message>,
+    }
+
+    impl<Message: Send + 'static> Operation<Result<(), crate::Error>> for Rollback<Message> {
+        fn custom(
+            &mut self,
+            id: Option<&iced_core::widget::Id>,
+            _bounds: Rectangle,
+            state: &mut dyn std::any::Any,
+        ) {
+            match id {
+                Some(id) if id == &self.id => {
+                    if let Some(state) = state.downcast_mut::<State<Message>>() {
+                        self.result = Some(state.rollback(self.steps));
+                    }
+
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        fn container(
+            &mut self,
+            _id: Option<&iced_core::widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Result<(), crate::Error>>),
+        ) {
+            operate_on_children(self)
+        }
+
+        fn finish(&self) -> operation::Outcome<Result<(), crate::Error>> {
+            outcome(&self.result)
+        }
+    }
+
+    task::widget(Rollback {
+        id: id.into().into(),
+        steps,
+        result: None,
+        message: PhantomData::<Message>,
+    })
+}
+
+pub fn patch<Message: Send + 'static>(
+    id: &Id,
+    literals: Vec<runtime::Literal>,
+    timer: Instant,
+) -> Task<Event> {
+    struct Patch<Message> {
+        id: iced_core::widget::Id,
+        literals: Vec<runtime::Literal>,
+        result: Option<Result<(), crate::Error>>,
+        message: PhantomData<Message>,
+    }
+
+    impl<Message: Send + 'static> Operation<Result<(), crate::Error>> for Patch<Message> {
+        fn custom(
+            &mut self,
+            id: Option<&iced_core::widget::Id>,
+            _bounds: Rectangle,
+            state: &mut dyn std::any::Any,
+        ) {
+            match id {
+                Some(id) if id == &self.id => {
+                    if let Some(state) = state.downcast_mut::<State<Message>>() {
+                        self.result = Some(state.patch(&self.literals));
+                    }
+
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        fn container(
+            &mut self,
+            _id: Option<&iced_core::widget::Id>,
+            _bounds: Rectangle,
+            operate_on_children: &mut dyn FnMut(&mut dyn Operation<Result<(), crate::Error>>),
+        ) {
+            operate_on_children(self)
+        }
+
+        fn finish(&self) -> operation::Outcome<Result<(), crate::Error>> {
+            outcome(&self.result)
+        }
+    }
+
+    task::widget(Patch {
+        id: id.0.clone(),
+        literals,
+        result: None,
+        message: PhantomData::<Message>,
+    })
+    .map(move |result| match result {
+        Ok(()) => Event::Patched(timer.elapsed()),
+        Err(error) => Event::Failed(error),
     })
 }
@@ -1,76 +1,257 @@
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
 
 use iced_widget::runtime::Task;
-use iced_widget::runtime::futures::futures::channel::mpsc::channel;
+use iced_widget::runtime::futures::futures::channel::mpsc::{self, channel};
 use iced_widget::runtime::futures::futures::{SinkExt, Stream, StreamExt};
 use iced_widget::runtime::futures::{futures, stream};
 use notify_debouncer_mini::notify::RecursiveMode;
-use notify_debouncer_mini::{DebouncedEventKind, new_debouncer};
-use proc_macro2::TokenStream;
+use notify_debouncer_mini::{DebounceEventResult, DebouncedEventKind, new_debouncer};
+use proc_macro2::{LineColumn, TokenStream};
 use quote::{ToTokens, quote};
 use syn::visit::{self, Visit};
 
 use crate::error::MacroError;
-use crate::task::executor;
+use crate::runtime::Literal;
+use crate::task::generation::Counter;
+use crate::task::literal::Literals;
+use crate::task::module::{Dependencies, Module};
+use crate::task::span::SpanMap;
+use crate::task::{component, executor, import};
+use crate::widget::Id;
+use crate::{Config, Dependency};
 
-pub fn init_directory() -> Task<Result<PathBuf, crate::Error>> {
-    executor::try_spawn_blocking(|mut sender| {
-        let manifest = tempfile::tempdir()?.keep().join("component");
+static RETRIES: LazyLock<Mutex<HashMap<Id, mpsc::UnboundedSender<()>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
 
+/// The hash and shape of the last [`Source`] of every widget that reloaded successfully.
+static RELOADED: LazyLock<Mutex<HashMap<Id, (u64, u64)>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Where a `thawing::view!` was invoked, as captured by the macro.
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Identifies a `thawing::view!` of a file by the items enclosing it,
+/// so it can be found again after the code around it changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ViewKey {
+    scope: String,
+    index: usize,
+}
+
+impl std::fmt::Display for ViewKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.index {
+            0 => write!(f, "{}", self.scope),
+            index => write!(f, "{}#{index}", self.scope),
+        }
+    }
+}
+
+/// The generated crate of a single view.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub manifest: PathBuf,
+    pub view: ViewKey,
+    pub guest: Dependency,
+}
+
+pub fn init_directory(config: &Config, caller: &Caller) -> Task<Result<Generated, crate::Error>> {
+    let config = config.clone();
+    let caller = caller.clone();
+
+    executor::try_spawn_blocking(move |mut sender| {
         let timer = std::time::Instant::now();
-        fs::create_dir(&manifest)?;
 
-        let src_path = manifest.join("src");
-        fs::create_dir(&src_path)?;
+        let file = syn::parse_file(&fs::read_to_string(&caller.path)?)?;
+        let view = locate(&file, caller.line, caller.column)?;
+        let metadata = Metadata::of(&caller.path);
+        let cache_dir = config
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| default_cache_dir(metadata.as_ref(), &caller.path));
+        let manifest = cache_dir.join(directory_name(&caller.path, &view));
 
-        let target_path = manifest.join("target");
-        fs::create_dir(&target_path)?;
+        let guest = match config.guest.clone() {
+            Some(guest) => guest,
+            None => find_guest(metadata.as_ref())?,
+        };
+        tracing::info!("Using `thawing_guest` from {guest:?}");
 
-        fs::File::create(src_path.join("lib.rs"))?;
+        let toml = component_toml(&config.artifact, &guest);
 
-        let mut toml_file = fs::File::create(manifest.join("Cargo.toml"))?;
-        toml_file.write_all(COMPONENT_TOML.as_bytes())?;
+        create_private_dir(&cache_dir)?;
+        fs::create_dir_all(manifest.join("src"))?;
+        fs::create_dir_all(manifest.join("target"))?;
 
-        tracing::info!("Creating `component` tempdir took {:?}", timer.elapsed());
+        let toml_path = manifest.join("Cargo.toml");
+        if fs::read_to_string(&toml_path).ok().as_deref() != Some(toml.as_str()) {
+            let mut toml_file = fs::File::create(toml_path)?;
+            toml_file.write_all(toml.as_bytes())?;
+        }
 
-        let _ = sender.try_send(manifest);
+        tracing::info!(
+            "Preparing `component` directory at {manifest:?} took {:?}",
+            timer.elapsed()
+        );
+
+        let _ = sender.try_send(Generated {
+            manifest,
+            view,
+            guest,
+        });
 
         Ok(())
     })
 }
 
+/// The generated crate, along with how its `lib.rs` maps back to the caller.
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub manifest: PathBuf,
+    pub guest: Dependency,
+    pub spans: Arc<SpanMap>,
+    /// The hash of the extracted guest code and its dependencies.
+    pub hash: u64,
+    /// The same hash, but ignoring the values of the literals of the view.
+    pub shape: u64,
+    pub literals: Vec<Literal>,
+}
+
+impl Source {
+    pub fn is_unchanged(&self, id: &Id) -> bool {
+        RELOADED
+            .lock()
+            .unwrap()
+            .get(id)
+            .is_some_and(|(hash, _)| *hash == self.hash)
+    }
+
+    /// Whether only literals of the view changed since the last reload.
+    pub fn is_patchable(&self, id: &Id) -> bool {
+        RELOADED
+            .lock()
+            .unwrap()
+            .get(id)
+            .is_some_and(|(_, shape)| *shape == self.shape)
+    }
+
+    pub fn reloaded(&self, id: &Id) {
+        RELOADED
+            .lock()
+            .unwrap()
+            .insert(id.clone(), (self.hash, self.shape));
+    }
+}
+
+/// Forgets the last [`Source`] the widget reloaded, since its component no
+/// longer matches it, so its next change is built whatever it is.
+pub fn forget(id: &Id) {
+    RELOADED.lock().unwrap().remove(id);
+}
+
 pub fn parse_and_write(
-    caller: &PathBuf,
-    manifest: Result<PathBuf, crate::Error>,
-) -> Task<Result<PathBuf, crate::Error>> {
-    let manifest = match manifest {
+    caller: &Path,
+    generated: Result<Generated, crate::Error>,
+    config: &Config,
+) -> Task<Result<Source, crate::Error>> {
+    let Generated {
+        manifest,
+        view,
+        guest,
+    } = match generated {
         Err(error) => return Task::done(Err(error)),
-        Ok(manifest) => manifest.to_path_buf(),
+        Ok(generated) => generated,
     };
 
-    let caller = caller.to_path_buf();
+    let dependencies = Dependencies::new(caller, config);
     let target = manifest.join("src").join("lib.rs");
 
     executor::try_spawn_blocking(move |mut sender| {
         let timer = std::time::Instant::now();
-        let content = fs::read_to_string(caller)?;
-        let caller = syn::parse_file(&content)?;
+        let modules = dependencies.modules()?;
         let ParsedFile {
             data,
             message,
+            message_ty,
             state,
             state_ty,
+            helpers,
+            update,
             view,
-        } = FileParser::from_file(&caller).build()?;
+            function,
+            binding,
+        } = FileParser::parse(&modules, &view)?;
+
+        let mut used = HashSet::new();
+        for chunk in data
+            .iter()
+            .chain([&message, &state])
+            .chain(&helpers)
+            .chain(update.as_ref().map(|update| &update.definition))
+            .chain([&view])
+        {
+            import::idents(&chunk.tokens, &mut used);
+        }
+        let imports = import::translate(&modules, &used)?;
+
+        let literals = Literals::extract(&view.tokens);
+        let patched = &literals.view;
+        let binding = binding.map(|pat| quote!(let #pat = self;));
+        let body = if function {
+            quote! {
+                let view: Element<'_, Self::Message> = {
+                    #binding
+                    #patched
+                };
+                view
+            }
+        } else {
+            quote!(#patched)
+        };
+
+        let (definition, apply) = match &update {
+            Some(Update {
+                definition,
+                name,
+                method,
+            }) => {
+                let (definition, call) = if *method {
+                    (quote!(impl #state_ty { #definition }), quote!(Self::#name))
+                } else {
+                    (quote!(#definition), quote!(#name))
+                };
+
+                (
+                    definition,
+                    quote! {
+                        const UPDATE: bool = true;
+
+                        fn update(&mut self, message: Self::Message) -> bool {
+                            #call(self, message);
+                            true
+                        }
+                    },
+                )
+            }
+            None => (quote!(), quote!()),
+        };
 
         let output = quote! {
             #![allow(unused_imports)]
-            use thawing_guest::thawing;
-            use thawing_guest::widget::{button, checkbox, column, text, Style};
-            use thawing_guest::{Application, Center, Element, Color, Theme, color};
+            use thawing_guest::prelude::*;
+            #(#imports)*
 
             #(#data)*
 
@@ -78,75 +259,172 @@ pub fn parse_and_write(
 
             #state
 
+            #(#helpers)*
+
+            #definition
+
             impl Application for #state_ty {
-                fn view(&self) -> impl Into<Element> {
-                    #view
+                type Message = #message_ty;
+
+                fn view(&self) -> impl Into<Element<'_, Self::Message>> {
+                    #body
                 }
+
+                #apply
             }
 
             thawing_guest::thaw!(#state_ty);
         };
 
-        let content = prettyplease::unparse(&syn::parse_file(&output.to_string())?);
-        tracing::info!("Wrote:\n{content}");
+        let output = output.to_string();
+        let toml = fs::read(manifest.join("Cargo.toml"))?;
+        let extras = dependencies
+            .extras()
+            .into_iter()
+            .map(|path| fs::read(&path).map(|content| (path, content)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A local guest can change without anything else changing
+        let guest_sources = match &guest {
+            Dependency::Path(path) => {
+                let mut hasher = DefaultHasher::new();
+                component::hash_sources(path, &mut hasher)?;
+                Some(hasher.finish())
+            }
+            Dependency::Git { .. } => None,
+        };
+
+        let mut hasher = DefaultHasher::new();
+        output.hash(&mut hasher);
+        toml.hash(&mut hasher);
+        extras.hash(&mut hasher);
+        guest_sources.hash(&mut hasher);
+
+        let shape = {
+            let view = &literals.shape;
+            let mut hasher = DefaultHasher::new();
+
+            quote! { #(#imports)* #(#data)* #message #state #(#helpers)* #definition #state_ty #view }
+                .to_string()
+                .hash(&mut hasher);
+            toml.hash(&mut hasher);
+            extras.hash(&mut hasher);
+            guest_sources.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let content = prettyplease::unparse(&syn::parse_file(&output)?);
+        tracing::debug!("Wrote:\n{content}");
         let mut lib_file = fs::File::create(target)?;
         lib_file.write_all(content.as_bytes())?;
         lib_file.sync_data()?;
 
+        let chunks: Vec<_> = data
+            .iter()
+            .chain([&message, &state])
+            .chain(&helpers)
+            .chain(update.as_ref().map(|update| &update.definition))
+            .chain([&view])
+            .map(|chunk| (chunk.module, &chunk.tokens))
+            .collect();
+        let files = modules
+            .into_iter()
+            .map(|module| (module.path, module.source))
+            .collect();
+        let spans = SpanMap::new(files, &chunks, &content);
+
         tracing::info!(
             "Parsing and writing to `component` took {:?}",
             timer.elapsed()
         );
 
-        let _ = sender.try_send(manifest);
+        let _ = sender.try_send(Source {
+            manifest,
+            guest,
+            spans: Arc::new(spans),
+            hash: hasher.finish(),
+            shape,
+            literals: literals.values,
+        });
 
         Ok(())
     })
 }
 
-pub fn watch(path: impl AsRef<Path>) -> impl Stream<Item = ()> {
-    let src_path = path.as_ref().to_path_buf();
+pub fn watch(dependencies: Dependencies, counter: Counter) -> impl Stream<Item = ()> {
+    let watched = dependencies.watched();
 
     stream::channel(
         10,
         move |mut output: futures::channel::mpsc::Sender<()>| async move {
             let (mut tx, mut rx) = channel(1);
 
-            let mut debouncer = new_debouncer(Duration::from_millis(500), move |res| {
-                futures::executor::block_on(async {
-                    tx.send(res).await.expect("Failed to send debounce event");
-                })
-            })
-            .expect("Failed to create file watcher");
+            let mut debouncer = new_debouncer(
+                Duration::from_millis(500),
+                move |res: DebounceEventResult| {
+                    let affected = res.is_ok_and(|events| {
+                        dependencies.is_affected_by(
+                            events
+                                .iter()
+                                .filter(|event| event.kind == DebouncedEventKind::Any)
+                                .map(|event| event.path.as_path()),
+                        )
+                    });
 
-            debouncer
-                .watcher()
-                .watch(&src_path, RecursiveMode::Recursive)
-                .expect("Failed to watch path");
-
-            tracing::info!("Watching {src_path:?}");
-
-            loop {
-                for _ in rx
-                    .next()
-                    .await
-                    .map(Result::ok)
-                    .flatten()
-                    .into_iter()
-                    .flat_map(|events| {
-                        events
-                            .into_iter()
-                            .filter(|event| event.kind == DebouncedEventKind::Any)
+                    if !affected {
+                        return;
+                    }
+
+                    // Bumped right away, so a running build is cancelled even
+                    // before this change is picked up
+                    counter.bump();
+
+                    futures::executor::block_on(async {
+                        tx.send(()).await.expect("Failed to send debounce event");
                     })
-                    .collect::<Vec<_>>()
-                {
-                    output.send(()).await.expect("Failed to send message");
+                },
+            )
+            .expect("Failed to create file watcher");
+
+            for (path, recursive) in watched {
+                let mode = if recursive {
+                    RecursiveMode::Recursive
+                } else {
+                    RecursiveMode::NonRecursive
+                };
+
+                match debouncer.watcher().watch(&path, mode) {
+                    Ok(()) => tracing::info!("Watching {path:?}"),
+                    // An include glob may not match anything yet
+                    Err(error) => tracing::warn!("Failed to watch {path:?}: {error}"),
                 }
             }
+
+            while let Some(()) = rx.next().await {
+                output.send(()).await.expect("Failed to send message");
+            }
         },
     )
 }
 
+/// Rebuilds the widget with the given [`Id`], as if its file had changed.
+pub fn retry(id: &Id) {
+    // Asked for explicitly, so it is never skipped as unchanged
+    forget(id);
+    Counter::of(id).bump();
+
+    if let Some(sender) = RETRIES.lock().unwrap().get(id) {
+        let _ = sender.unbounded_send(());
+    }
+}
+
+pub fn retries(id: Id) -> impl Stream<Item = ()> {
+    let (sender, receiver) = mpsc::unbounded();
+    RETRIES.lock().unwrap().insert(id, sender);
+
+    receiver
+}
+
 enum TypeDef<'ast> {
     Enum(&'ast syn::ItemEnum),
     Struct(&'ast syn::ItemStruct),
@@ -161,63 +439,387 @@ impl<'ast> quote::ToTokens for TypeDef<'ast> {
     }
 }
 
+impl<'ast> TypeDef<'ast> {
+    fn ident(&self) -> &'ast syn::Ident {
+        match self {
+            TypeDef::Enum(node) => &node.ident,
+            TypeDef::Struct(node) => &node.ident,
+        }
+    }
+}
+
+enum Helper<'ast> {
+    Fn(&'ast syn::ItemFn),
+    Impl(&'ast syn::ItemImpl),
+}
+
+impl<'ast> quote::ToTokens for Helper<'ast> {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Helper::Fn(node) => node.to_tokens(tokens),
+            // Views and updates are extracted on their own
+            Helper::Impl(node) => {
+                let mut node = (*node).clone();
+                node.items.retain(|item| match item {
+                    syn::ImplItem::Fn(function) => !function
+                        .attrs
+                        .iter()
+                        .any(|attr| is_view_fn(attr) || is_update(attr)),
+                    _ => true,
+                });
+                node.to_tokens(tokens);
+            }
+        }
+    }
+}
+
+/// A hot reloadable view: a `thawing::view!`, the body of a `#[thawing::view_fn]`,
+/// or the view function passed to `thawing::application`.
+#[derive(Clone, Copy)]
+enum View<'ast> {
+    Macro(&'ast syn::Macro),
+    Function {
+        attr: &'ast syn::Attribute,
+        block: &'ast syn::Block,
+    },
+    Application(&'ast syn::ExprCall),
+}
+
+impl<'ast> View<'ast> {
+    /// Where `line!` and `column!` point at, when invoked by the view.
+    fn start(&self) -> Option<LineColumn> {
+        match self {
+            View::Macro(node) => node
+                .path
+                .segments
+                .first()
+                .map(|segment| segment.ident.span().start()),
+            View::Function { attr, .. } => Some(attr.pound_token.span.start()),
+            View::Application(call) => match call.func.as_ref() {
+                syn::Expr::Path(path) => path
+                    .path
+                    .segments
+                    .first()
+                    .map(|segment| segment.ident.span().start()),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// A free function, or a method of the type with the given name.
+enum Function<'ast> {
+    Free(&'ast syn::ItemFn),
+    Method(String, &'ast syn::ImplItemFn),
+}
+
+impl<'ast> Function<'ast> {
+    fn sig(&self) -> &'ast syn::Signature {
+        match self {
+            Function::Free(node) => &node.sig,
+            Function::Method(_, node) => &node.sig,
+        }
+    }
+
+    fn block(&self) -> &'ast syn::Block {
+        match self {
+            Function::Free(node) => &node.block,
+            Function::Method(_, node) => &node.block,
+        }
+    }
+
+    /// The function as defined, without the attributes of `thawing`.
+    fn definition(&self) -> TokenStream {
+        match self {
+            Function::Free(node) => {
+                let mut node = (*node).clone();
+                node.attrs.retain(|attr| !is_thawing(attr));
+                node.to_token_stream()
+            }
+            Function::Method(_, node) => {
+                let mut node = (*node).clone();
+                node.attrs.retain(|attr| !is_thawing(attr));
+                node.to_token_stream()
+            }
+        }
+    }
+
+    /// The pattern the first argument is bound to, unless it is `self`.
+    fn binding(&self) -> Option<&'ast syn::Pat> {
+        match self.sig().inputs.first()? {
+            syn::FnArg::Receiver(_) => None,
+            syn::FnArg::Typed(argument) => Some(&argument.pat),
+        }
+    }
+}
+
+/// An update function copied into the component.
+struct Update {
+    definition: Chunk,
+    name: syn::Ident,
+    /// Whether it is a method of the state, rather than a free function.
+    method: bool,
+}
+
 struct FileParser<'ast> {
-    file: &'ast syn::File,
-    view: Option<&'ast syn::Macro>,
+    /// The names of the items enclosing the node being visited.
+    scope: Vec<String>,
+    views: Vec<(ViewKey, View<'ast>)>,
     state_ty: Option<&'ast syn::Ident>,
     state: Option<TypeDef<'ast>>,
     message: Option<TypeDef<'ast>>,
     data: Vec<TypeDef<'ast>>,
+    helpers: Vec<Helper<'ast>>,
+    functions: Vec<Function<'ast>>,
+}
+
+/// Tokens extracted from the module with the given index.
+struct Chunk {
+    module: usize,
+    tokens: TokenStream,
+}
+
+impl Chunk {
+    fn new(module: usize, tokens: impl ToTokens) -> Self {
+        Self {
+            module,
+            tokens: tokens.to_token_stream(),
+        }
+    }
+}
+
+impl quote::ToTokens for Chunk {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.tokens.to_tokens(tokens);
+    }
 }
+
 struct ParsedFile {
-    view: TokenStream,
-    state: TokenStream,
+    view: Chunk,
+    /// Whether the view is the body of a function, rather than a macro.
+    function: bool,
+    /// What the first argument of a view function is bound to, unless it is `self`.
+    binding: Option<syn::Pat>,
+    state: Chunk,
     state_ty: syn::Ident,
-    message: TokenStream,
-    data: Vec<TokenStream>,
+    message: Chunk,
+    message_ty: syn::Ident,
+    data: Vec<Chunk>,
+    helpers: Vec<Chunk>,
+    update: Option<Update>,
 }
 
 impl<'ast> FileParser<'ast> {
-    fn build(mut self) -> Result<ParsedFile, crate::Error> {
-        self.visit_file(&self.file);
-
-        let data = self.data.iter().map(ToTokens::to_token_stream).collect();
-        let view = self
-            .view
-            .ok_or(MacroError::ViewMacroMissing)?
-            .tokens
-            .clone();
-        let state = self
-            .state
-            .ok_or(MacroError::StateAttributeMissing)?
-            .to_token_stream();
-        let state_ty = self
+    /// Extracts the given view of the first module, along with the types
+    /// and helpers of every module.
+    ///
+    /// The state and message are taken from the first module defining them.
+    fn parse(modules: &'ast [Module], view: &ViewKey) -> Result<ParsedFile, crate::Error> {
+        let parsers: Vec<_> = modules
+            .iter()
+            .map(|module| Self::from_file(&module.file))
+            .collect();
+
+        let (_, view) = parsers
+            .first()
+            .and_then(|parser| parser.views.iter().find(|(key, _)| key == view))
+            .ok_or_else(|| MacroError::ViewNotFound(view.to_string()))?;
+
+        let (module, parser) = parsers
+            .iter()
+            .enumerate()
+            .find(|(_, parser)| parser.state.is_some())
+            .ok_or(MacroError::StateAttributeMissing)?;
+        let state = Chunk::new(module, &parser.state);
+        let state_ty = parser
             .state_ty
             .ok_or(MacroError::StateAttributeMissing)?
             .clone();
-        let message = self
+
+        let (module, parser) = parsers
+            .iter()
+            .enumerate()
+            .find(|(_, parser)| parser.message.is_some())
+            .ok_or(MacroError::MessageAttributeMissing)?;
+        let message = Chunk::new(module, &parser.message);
+        let message_ty = parser
             .message
+            .as_ref()
+            .map(TypeDef::ident)
             .ok_or(MacroError::MessageAttributeMissing)?
-            .to_token_stream();
+            .clone();
+
+        let types: Vec<_> = parsers
+            .iter()
+            .flat_map(|parser| parser.message.iter().chain(&parser.data))
+            .map(TypeDef::ident)
+            .collect();
+
+        // Only types copied into the component can have helper methods
+        for helper in parsers.iter().flat_map(|parser| &parser.helpers) {
+            let Helper::Impl(node) = helper else {
+                continue;
+            };
+
+            let known = match node.self_ty.as_ref() {
+                syn::Type::Path(path) => path
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| *ident == state_ty || types.contains(&ident)),
+                _ => false,
+            };
+
+            if !known {
+                let ty = node.self_ty.to_token_stream().to_string();
+                return Err(MacroError::HelperTypeUnknown(ty).into());
+            }
+        }
+
+        let data = parsers
+            .iter()
+            .enumerate()
+            .flat_map(|(module, parser)| {
+                parser.data.iter().map(move |data| Chunk::new(module, data))
+            })
+            .collect();
+        let helpers = parsers
+            .iter()
+            .enumerate()
+            .flat_map(|(module, parser)| {
+                parser
+                    .helpers
+                    .iter()
+                    .map(move |helper| Chunk::new(module, helper))
+            })
+            .collect();
+
+        let (view, function, binding, update) = match view {
+            View::Macro(node) => (Chunk::new(0, &node.tokens), false, None, None),
+            View::Function { block, .. } => (Chunk::new(0, block), true, None, None),
+            View::Application(call) => {
+                let argument = |index: usize| {
+                    call.args
+                        .iter()
+                        .nth(index)
+                        .and_then(|argument| Self::resolve(&parsers, argument))
+                        .ok_or_else(|| {
+                            let argument = call.args.iter().nth(index);
+                            MacroError::ApplicationFunction(argument.to_token_stream().to_string())
+                        })
+                };
+
+                let (module, view) = argument(2)?;
+                let (update_module, update) = argument(1)?;
+
+                // Only the new state can be taken back from the component
+                let update = match update.sig().output {
+                    syn::ReturnType::Default => Some(Update {
+                        definition: Chunk::new(update_module, update.definition()),
+                        name: update.sig().ident.clone(),
+                        method: matches!(update, Function::Method(..)),
+                    }),
+                    syn::ReturnType::Type(..) => {
+                        tracing::info!("The update returns a value, so it is not hot reloaded");
+                        None
+                    }
+                };
+
+                (
+                    Chunk::new(module, view.block()),
+                    true,
+                    view.binding().cloned(),
+                    update,
+                )
+            }
+        };
+
+        // Otherwise, the method marked with `#[thawing::update]`
+        let update = update.or_else(|| {
+            parsers.iter().enumerate().find_map(|(module, parser)| {
+                parser.functions.iter().find_map(|function| match function {
+                    Function::Method(ty, node)
+                        if state_ty == ty && node.attrs.iter().any(is_update) =>
+                    {
+                        Some(Update {
+                            definition: Chunk::new(module, function.definition()),
+                            name: node.sig.ident.clone(),
+                            method: true,
+                        })
+                    }
+                    _ => None,
+                })
+            })
+        });
 
         Ok(ParsedFile {
             data,
             view,
+            function,
+            binding,
             state,
             state_ty,
             message,
+            message_ty,
+            helpers,
+            update,
+        })
+    }
+
+    /// Finds the function a path points to, e.g. `Counter::view` or `view`.
+    fn resolve<'a>(
+        parsers: &'a [FileParser<'ast>],
+        path: &syn::Expr,
+    ) -> Option<(usize, &'a Function<'ast>)> {
+        let syn::Expr::Path(path) = path else {
+            return None;
+        };
+
+        let segments: Vec<_> = path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+
+        let find = |matches: &dyn Fn(&Function<'ast>) -> bool| {
+            parsers.iter().enumerate().find_map(|(module, parser)| {
+                parser
+                    .functions
+                    .iter()
+                    .find(|function| matches(function))
+                    .map(|function| (module, function))
+            })
+        };
+
+        // Methods first, since a free function may share their name
+        find(&|function| match (function, segments.as_slice()) {
+            (Function::Method(ty, node), [.., owner, name]) => {
+                owner == ty && node.sig.ident == name
+            }
+            _ => false,
+        })
+        .or_else(|| {
+            find(&|function| match (function, segments.as_slice()) {
+                (Function::Free(node), [.., name]) => node.sig.ident == name,
+                _ => false,
+            })
         })
     }
 
     fn from_file(file: &'ast syn::File) -> Self {
-        Self {
-            file,
+        let mut parser = Self {
             data: vec![],
-            view: None,
+            helpers: vec![],
+            functions: vec![],
+            scope: vec![],
+            views: vec![],
             state_ty: None,
             state: None,
             message: None,
-        }
+        };
+
+        parser.visit_file(file);
+        parser
     }
 }
 
@@ -288,6 +890,59 @@ impl<'ast> Visit<'ast> for FileParser<'ast> {
         visit::visit_item_enum(self, node);
     }
 
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if node.attrs.iter().any(is_helper) {
+            self.helpers.push(Helper::Fn(node));
+        }
+        self.functions.push(Function::Free(node));
+
+        self.scope.push(node.sig.ident.to_string());
+        self.function(&node.attrs, &node.block);
+        visit::visit_item_fn(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if node.attrs.iter().any(is_helper) {
+            self.helpers.push(Helper::Impl(node));
+        }
+
+        let ty = node.self_ty.to_token_stream().to_string().replace(' ', "");
+        self.scope.push(ty);
+        visit::visit_item_impl(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        let ty = self.scope.last().cloned().unwrap_or_default();
+        self.functions.push(Function::Method(ty, node));
+
+        self.scope.push(node.sig.ident.to_string());
+        self.function(&node.attrs, &node.block);
+        visit::visit_impl_item_fn(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        self.scope.push(node.ident.to_string());
+        visit::visit_item_mod(self, node);
+        self.scope.pop();
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        if let syn::Expr::Path(path) = node.func.as_ref() {
+            let segments = &path.path.segments;
+
+            if segments.first().is_some_and(|p| p.ident == "thawing")
+                && segments.last().is_some_and(|p| p.ident == "application")
+            {
+                self.push_view(View::Application(node));
+            }
+        }
+
+        visit::visit_expr_call(self, node);
+    }
+
     fn visit_macro(&mut self, node: &'ast syn::Macro) {
         if node
             .path
@@ -302,22 +957,223 @@ impl<'ast> Visit<'ast> for FileParser<'ast> {
                 .map(|p| p.ident.to_string())
                 .is_some_and(|ident| &ident == "view")
         {
-            self.view = Some(node);
+            self.push_view(View::Macro(node));
         }
 
         visit::visit_macro(self, node);
     }
 }
 
-const COMPONENT_TOML: &'static str = r#"[package]
-name = "component"
+impl<'ast> FileParser<'ast> {
+    /// Records the body of a `#[thawing::view_fn]`, within the scope of the function.
+    fn function(&mut self, attrs: &'ast [syn::Attribute], block: &'ast syn::Block) {
+        if let Some(attr) = attrs.iter().find(|attr| is_view_fn(attr)) {
+            self.push_view(View::Function { attr, block });
+        }
+    }
+
+    fn push_view(&mut self, view: View<'ast>) {
+        let scope = self.scope.join("::");
+        let index = self
+            .views
+            .iter()
+            .filter(|(key, _)| key.scope == scope)
+            .count();
+
+        self.views.push((ViewKey { scope, index }, view));
+    }
+}
+
+/// Finds the `thawing::view!`, or `#[thawing::view_fn]`, invoked at the given position.
+///
+/// `line!` and `column!` point at the start of the outermost macro invocation,
+/// with a 1-based column.
+fn locate(file: &syn::File, line: u32, column: u32) -> Result<ViewKey, crate::Error> {
+    let parser = FileParser::from_file(file);
+
+    let on_line = |view: &View<'_>| {
+        view.start()
+            .is_some_and(|start| start.line == line as usize)
+    };
+
+    parser
+        .views
+        .iter()
+        .find(|(_, view)| {
+            view.start().is_some_and(|start| {
+                start.line == line as usize && start.column + 1 == column as usize
+            })
+        })
+        .or_else(|| parser.views.iter().find(|(_, view)| on_line(view)))
+        .map(|(key, _)| key.clone())
+        .ok_or_else(|| MacroError::ViewMacroMissing.into())
+}
+
+fn is_helper(attr: &syn::Attribute) -> bool {
+    let segments = &attr.path().segments;
+
+    segments.first().is_some_and(|p| p.ident == "thawing")
+        && segments.last().is_some_and(|p| p.ident == "helper")
+}
+
+fn is_thawing(attr: &syn::Attribute) -> bool {
+    attr.path()
+        .segments
+        .first()
+        .is_some_and(|p| p.ident == "thawing")
+}
+
+fn is_update(attr: &syn::Attribute) -> bool {
+    let segments = &attr.path().segments;
+
+    segments.first().is_some_and(|p| p.ident == "thawing")
+        && segments.last().is_some_and(|p| p.ident == "update")
+}
+
+fn is_view_fn(attr: &syn::Attribute) -> bool {
+    let segments = &attr.path().segments;
+
+    segments.first().is_some_and(|p| p.ident == "thawing")
+        && segments.last().is_some_and(|p| p.ident == "view_fn")
+}
+
+fn find_guest(metadata: Option<&Metadata>) -> Result<Dependency, crate::Error> {
+    metadata
+        .and_then(Metadata::guest)
+        .or_else(|| {
+            // Cargo never packages nested crates, so this only exists when
+            // `thawing` itself is a path or git dependency
+            let bundled = Path::new(env!("CARGO_MANIFEST_DIR")).join("guest");
+            bundled.join("Cargo.toml").exists().then_some(bundled)
+        })
+        .map(Dependency::Path)
+        .ok_or(crate::Error::GuestNotFound)
+}
+
+/// The workspace of the caller, as reported by `cargo metadata`.
+#[derive(serde::Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    target_directory: PathBuf,
+}
+
+#[derive(serde::Deserialize)]
+struct Package {
+    name: String,
+    manifest_path: PathBuf,
+}
+
+impl Metadata {
+    fn of(caller: &Path) -> Option<Self> {
+        let output = Command::new("cargo")
+            .args([
+                "metadata",
+                "--format-version",
+                "1",
+                "--no-deps",
+                "--offline",
+            ])
+            .current_dir(caller.parent()?)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        serde_json::from_slice(&output.stdout).ok()
+    }
+
+    /// The `thawing_guest` of the workspace, if it is a member.
+    fn guest(&self) -> Option<PathBuf> {
+        self.packages
+            .iter()
+            .find(|package| package.name == "thawing_guest")
+            .and_then(|package| package.manifest_path.parent().map(Path::to_path_buf))
+    }
+}
+
+/// The directory in the target directory of the caller's crate, so it stays
+/// private to the user and is cleaned along with the rest of the build.
+fn default_cache_dir(metadata: Option<&Metadata>, caller: &Path) -> PathBuf {
+    let target = metadata
+        .map(|metadata| metadata.target_directory.clone())
+        .unwrap_or_else(|| {
+            let parent = caller.parent().unwrap_or(Path::new("/"));
+
+            parent
+                .ancestors()
+                .find(|directory| directory.join("Cargo.toml").exists())
+                .unwrap_or(parent)
+                .join("target")
+        });
+
+    target.join("thawing")
+}
+
+/// Creates the directory and its parents, only accessible by the current user,
+/// since the precompiled components in it are loaded as native code.
+///
+/// Fails if the directory already exists, but is writable by other users.
+pub fn create_private_dir(path: &Path) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    let _ = builder.recursive(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+
+        let _ = builder.mode(0o700);
+    }
+
+    builder.create(path)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+
+        let metadata = fs::metadata(path)?;
+
+        // SAFETY: `geteuid` is always successful, and has no side effects.
+        if metadata.uid() != unsafe { libc::geteuid() } || metadata.mode() & 0o022 != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("{path:?} is writable by other users"),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn directory_name(caller: &Path, view: &ViewKey) -> String {
+    let mut hasher = DefaultHasher::new();
+    caller.hash(&mut hasher);
+    view.hash(&mut hasher);
+
+    let stem = caller
+        .file_stem()
+        .map(|stem| stem.to_string_lossy())
+        .unwrap_or_default();
+
+    format!("{stem}-{:016x}", hasher.finish())
+}
+
+fn component_toml(name: &str, guest: &Dependency) -> String {
+    let guest = guest.to_toml();
+
+    format!(
+        r#"[package]
+name = "{name}"
 version = "0.1.0"
 edition = "2024"
 
 [workspace]
 
 [dependencies]
-thawing_guest = { git = "ssh://github.com/derezzedex/thawing" }
+thawing_guest = {guest}
 
 [lib]
 crate-type = ["cdylib"]
@@ -331,5 +1187,7 @@ lto = true
 
 [package.metadata.component]
 package = "thawing:component"
-dependencies = {}
-"#;
+dependencies = {{}}
+"#
+    )
+}
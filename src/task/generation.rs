@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use crate::widget::Id;
+
+static COUNTERS: LazyLock<Mutex<HashMap<Id, Counter>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Counts the changes noticed for a widget.
+///
+/// Every build starts at the current [`Generation`], and is cancelled
+/// as soon as a newer change bumps the counter.
+#[derive(Debug, Clone, Default)]
+pub struct Counter(Arc<AtomicU64>);
+
+impl Counter {
+    pub fn of(id: &Id) -> Self {
+        COUNTERS
+            .lock()
+            .unwrap()
+            .entry(id.clone())
+            .or_default()
+            .clone()
+    }
+
+    pub fn bump(&self) {
+        let _ = self.0.fetch_add(1, Ordering::SeqCst);
+    }
+
+    pub fn current(&self) -> Generation {
+        Generation {
+            value: self.0.load(Ordering::SeqCst),
+            counter: self.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Generation {
+    value: u64,
+    counter: Counter,
+}
+
+impl Generation {
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.counter.0.load(Ordering::SeqCst) != self.value
+    }
+}
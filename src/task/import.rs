@@ -0,0 +1,358 @@
+use std::collections::HashSet;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{format_ident, quote};
+
+use crate::error::{Location, ParserError};
+use crate::task::module::Module;
+
+/// The widgets of `iced::widget` the guest provides in `thawing_guest::widget`.
+const WIDGETS: &[&str] = &[
+    "button", "Button", "checkbox", "Checkbox", "column", "Column", "custom", "Custom", "text",
+    "Text",
+];
+
+/// The crates the generated crate can use, besides `thawing_guest` itself.
+const CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// The items of the `iced` root the guest provides in its own root.
+const ROOT: &[&str] = &[
+    "Center",
+    "Left",
+    "Right",
+    "Color",
+    "color",
+    "Element",
+    "Theme",
+    "Length",
+    "Fill",
+    "FillPortion",
+    "Shrink",
+    "Padding",
+    "Pixels",
+];
+
+/// Translates the `use` items of the modules into imports of the generated crate.
+///
+/// Imports of `iced` become their guest counterparts, while the standard library is
+/// kept as it is. The generated crate only depends on `thawing_guest`, so any other
+/// crate is unsupported. Only the names used by the extracted code are imported, so
+/// host-only items (e.g. `iced::Task`) are left out.
+pub fn translate(
+    modules: &[Module],
+    used: &HashSet<String>,
+) -> Result<Vec<TokenStream>, crate::Error> {
+    let mut imports = Vec::new();
+    let mut seen = HashSet::new();
+
+    for module in modules {
+        let mut paths = Vec::new();
+        for item in &module.file.items {
+            if let syn::Item::Use(node) = item {
+                flatten(&node.tree, Vec::new(), &mut paths);
+            }
+        }
+
+        for import in paths {
+            let translated = match &import.name {
+                Name::Glob => match translate_glob(&import.path) {
+                    Some(translated) => translated,
+                    None => {
+                        tracing::warn!(
+                            "Ignoring `{}::*`, it has no guest counterpart",
+                            import.path.join("::")
+                        );
+                        continue;
+                    }
+                },
+                Name::Item { name, alias } => {
+                    if !used.contains(alias.as_ref().unwrap_or(name)) {
+                        continue;
+                    }
+
+                    match translate_item(&import.path, name, alias.as_deref()) {
+                        Some(translated) => translated,
+                        None => {
+                            return Err(crate::Error::Parsing(ParserError::Unsupported {
+                                path: import
+                                    .path
+                                    .iter()
+                                    .chain([name])
+                                    .cloned()
+                                    .collect::<Vec<_>>()
+                                    .join("::"),
+                                location: Location {
+                                    file: module.path.clone(),
+                                    line: import.start.line,
+                                    column: import.start.column + 1,
+                                },
+                            }));
+                        }
+                    }
+                }
+            };
+
+            if let Some(translated) = translated
+                && seen.insert(translated.to_string())
+            {
+                imports.push(translated);
+            }
+        }
+    }
+
+    Ok(imports)
+}
+
+/// Collects the identifiers appearing in the tokens.
+pub fn idents(tokens: &TokenStream, idents: &mut HashSet<String>) {
+    for tree in tokens.clone() {
+        match tree {
+            TokenTree::Group(group) => self::idents(&group.stream(), idents),
+            TokenTree::Ident(ident) => {
+                let _ = idents.insert(ident.to_string());
+            }
+            TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+        }
+    }
+}
+
+struct Import {
+    path: Vec<String>,
+    name: Name,
+    start: proc_macro2::LineColumn,
+}
+
+enum Name {
+    Item { name: String, alias: Option<String> },
+    Glob,
+}
+
+/// Translates a `use` of a single item, returning `None` if it is unsupported,
+/// or `Some(None)` if it must not be imported.
+fn translate_item(path: &[String], name: &str, alias: Option<&str>) -> Option<Option<TokenStream>> {
+    let alias = alias.map(|alias| {
+        let alias = format_ident!("{alias}");
+        quote!(as #alias)
+    });
+
+    match path.first().map(String::as_str) {
+        Some("iced") => {
+            let full: Vec<_> = path[1..].iter().map(String::as_str).chain([name]).collect();
+
+            let guest = match full.as_slice() {
+                ["widget"] => vec!["widget"],
+                ["widget", widget] if WIDGETS.contains(widget) => vec!["widget", widget],
+                ["widget", "text", "Style"] => vec!["widget", "Style"],
+                ["alignment", "Horizontal", rest @ ..] => [&["Horizontal"][..], rest].concat(),
+                [root, rest @ ..] if ROOT.contains(root) => [&[*root][..], rest].concat(),
+                _ => return None,
+            };
+
+            let guest = guest.into_iter().map(|segment| format_ident!("{segment}"));
+            Some(Some(quote!(use thawing_guest::#(#guest)::* #alias;)))
+        }
+        // Items of the caller's crate are copied to the root of the generated crate
+        Some("crate" | "self" | "super") => Some(alias.map(|alias| {
+            let name = format_ident!("{name}");
+            quote!(use crate::#name #alias;)
+        })),
+        Some(krate) if CRATES.contains(&krate) => {
+            let path = path.iter().map(|segment| format_ident!("{segment}"));
+            let name = format_ident!("{name}");
+            Some(Some(quote!(use #(#path)::*::#name #alias;)))
+        }
+        _ => None,
+    }
+}
+
+fn translate_glob(path: &[String]) -> Option<Option<TokenStream>> {
+    let segments: Vec<_> = path.iter().map(String::as_str).collect();
+
+    match segments.as_slice() {
+        ["iced"] => Some(Some(quote!(
+            use thawing_guest::*;
+        ))),
+        ["iced", "widget"] => Some(Some(quote!(
+            use thawing_guest::widget::*;
+        ))),
+        ["iced", ..] => None,
+        ["crate" | "self" | "super", ..] => Some(None),
+        [krate, ..] if CRATES.contains(krate) => {
+            let path = segments.iter().map(|segment| format_ident!("{segment}"));
+            Some(Some(quote!(use #(#path)::*::*;)))
+        }
+        _ => None,
+    }
+}
+
+fn flatten(tree: &syn::UseTree, prefix: Vec<String>, imports: &mut Vec<Import>) {
+    match tree {
+        syn::UseTree::Path(path) => {
+            let mut prefix = prefix;
+            prefix.push(path.ident.to_string());
+            flatten(&path.tree, prefix, imports);
+        }
+        // `use iced::widget::{self}` imports `widget` itself
+        syn::UseTree::Name(name) if name.ident == "self" && !prefix.is_empty() => {
+            let start = name.ident.span().start();
+            let mut path = prefix;
+            let name = path.pop().unwrap_or_default();
+
+            imports.push(Import {
+                path,
+                name: Name::Item { name, alias: None },
+                start,
+            });
+        }
+        syn::UseTree::Name(name) => imports.push(Import {
+            path: prefix,
+            name: Name::Item {
+                name: name.ident.to_string(),
+                alias: None,
+            },
+            start: name.ident.span().start(),
+        }),
+        syn::UseTree::Rename(rename) if rename.ident == "self" && !prefix.is_empty() => {
+            let start = rename.ident.span().start();
+            let mut path = prefix;
+            let name = path.pop().unwrap_or_default();
+
+            imports.push(Import {
+                path,
+                name: Name::Item {
+                    name,
+                    alias: Some(rename.rename.to_string()),
+                },
+                start,
+            });
+        }
+        syn::UseTree::Rename(rename) => imports.push(Import {
+            path: prefix,
+            name: Name::Item {
+                name: rename.ident.to_string(),
+                alias: Some(rename.rename.to_string()),
+            },
+            start: rename.ident.span().start(),
+        }),
+        syn::UseTree::Glob(glob) => imports.push(Import {
+            path: prefix,
+            name: Name::Glob,
+            start: glob.star_token.span.start(),
+        }),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten(tree, prefix.clone(), imports);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    fn module(source: &str) -> Module {
+        Module {
+            path: PathBuf::from("src/main.rs"),
+            source: source.to_owned(),
+            file: syn::parse_file(source).unwrap(),
+        }
+    }
+
+    fn used(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn strings(imports: Vec<TokenStream>) -> Vec<String> {
+        imports.iter().map(TokenStream::to_string).collect()
+    }
+
+    #[test]
+    fn translates_the_used_iced_items() {
+        let modules = [module(
+            "use iced::widget::{button, column, text};\nuse iced::{Center, Element, Task};\n",
+        )];
+
+        let imports = translate(&modules, &used(&["button", "text", "Center", "Element"]));
+
+        assert_eq!(
+            strings(imports.unwrap()),
+            strings(vec![
+                quote!(
+                    use thawing_guest::widget::button;
+                ),
+                quote!(
+                    use thawing_guest::widget::text;
+                ),
+                quote!(
+                    use thawing_guest::Center;
+                ),
+                quote!(
+                    use thawing_guest::Element;
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn keeps_the_standard_library_and_skips_the_caller_crate() {
+        let modules = [module(
+            "use std::collections::HashMap;\nuse crate::state::Counter;\nuse super::helpers::*;\n",
+        )];
+
+        let imports = translate(&modules, &used(&["HashMap", "Counter"]));
+
+        assert_eq!(
+            strings(imports.unwrap()),
+            strings(vec![quote!(
+                use std::collections::HashMap;
+            )])
+        );
+    }
+
+    #[test]
+    fn rejects_the_used_items_of_other_crates() {
+        let modules = [module("use serde::*;\n\nuse serde_json::Value;\n")];
+
+        let Err(crate::Error::Parsing(ParserError::Unsupported { path, location })) =
+            translate(&modules, &used(&["Value"]))
+        else {
+            panic!("`serde_json::Value` must be unsupported");
+        };
+
+        assert_eq!(path, "serde_json::Value");
+        assert_eq!((location.line, location.column), (3, 17));
+    }
+
+    #[test]
+    fn flattens_groups_renames_and_self() {
+        let tree: syn::ItemUse =
+            syn::parse_str("use iced::{widget::{self, text as label}, *};").unwrap();
+
+        let mut imports = Vec::new();
+        flatten(&tree.tree, Vec::new(), &mut imports);
+
+        let flattened: Vec<_> = imports
+            .iter()
+            .map(|import| match &import.name {
+                Name::Item { name, alias } => (
+                    import.path.join("::"),
+                    Some(name.as_str()),
+                    alias.as_deref(),
+                ),
+                Name::Glob => (import.path.join("::"), None, None),
+            })
+            .collect();
+
+        assert_eq!(
+            flattened,
+            [
+                (String::from("iced"), Some("widget"), None),
+                (String::from("iced::widget"), Some("text"), Some("label")),
+                (String::from("iced"), None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::visit_mut::{self, VisitMut};
+
+use crate::runtime::Literal;
+
+/// The literals of a view, replaced by lookups into a table of the guest,
+/// so a change that only touches them can be patched in without a build.
+pub struct Literals {
+    pub view: TokenStream,
+    /// The view with every literal replaced by its kind, which stays
+    /// the same as long as only literal values change.
+    pub shape: TokenStream,
+    pub values: Vec<Literal>,
+}
+
+impl Literals {
+    pub fn extract(view: &TokenStream) -> Self {
+        let Ok(expr) = syn::parse2::<syn::Expr>(view.clone()) else {
+            return Self {
+                view: view.clone(),
+                shape: view.clone(),
+                values: Vec::new(),
+            };
+        };
+
+        let mut patched = expr.clone();
+        let mut replacer = Replacer {
+            shape: false,
+            values: Vec::new(),
+        };
+        replacer.visit_expr_mut(&mut patched);
+        let values = replacer.values;
+
+        let mut shape = expr;
+        Replacer {
+            shape: true,
+            values: Vec::new(),
+        }
+        .visit_expr_mut(&mut shape);
+
+        Self {
+            view: patched.into_token_stream(),
+            shape: shape.into_token_stream(),
+            values,
+        }
+    }
+}
+
+struct Replacer {
+    shape: bool,
+    values: Vec<Literal>,
+}
+
+impl Replacer {
+    fn replace(&mut self, lit: &syn::Lit) -> Option<syn::Expr> {
+        let (value, kind) = match lit {
+            syn::Lit::Int(int) => (
+                Literal::Integer(int.base10_parse().ok()?),
+                format!("integer{}", int.suffix()),
+            ),
+            syn::Lit::Float(float) => (
+                Literal::Float(float.base10_parse().ok()?),
+                format!("float{}", float.suffix()),
+            ),
+            syn::Lit::Str(text) => (Literal::Text(text.value()), String::from("text")),
+            syn::Lit::Char(character) => (
+                Literal::Character(character.value()),
+                String::from("character"),
+            ),
+            syn::Lit::Bool(boolean) => (Literal::Boolean(boolean.value), String::from("boolean")),
+            _ => return None,
+        };
+
+        let index = self.values.len() as u32;
+        self.values.push(value);
+
+        Some(if self.shape {
+            let kind = format_ident!("{kind}");
+            syn::parse_quote!(thawing_guest::literal::patch(#kind, #index))
+        } else {
+            syn::parse_quote!(thawing_guest::literal::patch(#lit, #index))
+        })
+    }
+}
+
+impl VisitMut for Replacer {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit { attrs, lit }) if attrs.is_empty() => {
+                if let Some(replaced) = self.replace(lit) {
+                    *expr = replaced;
+                }
+            }
+            syn::Expr::Macro(syn::ExprMacro { mac, .. }) => self.visit_macro_mut(mac),
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_macro_mut(&mut self, mac: &mut syn::Macro) {
+        // Only `column!`, the layout macro of the guest, takes plain expressions;
+        // the rest (e.g. `format!`) may need their literals at compile time
+        if !mac.path.is_ident("column") {
+            return;
+        }
+
+        let Ok(mut children) =
+            mac.parse_body_with(Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated)
+        else {
+            return;
+        };
+
+        for child in children.iter_mut() {
+            self.visit_expr_mut(child);
+        }
+
+        mac.tokens = quote!(#children);
+    }
+
+    fn visit_expr_repeat_mut(&mut self, repeat: &mut syn::ExprRepeat) {
+        // The length of an array must be a constant
+        self.visit_expr_mut(&mut repeat.expr);
+    }
+
+    fn visit_generic_argument_mut(&mut self, _argument: &mut syn::GenericArgument) {}
+
+    fn visit_type_mut(&mut self, _type: &mut syn::Type) {}
+
+    // Patterns (e.g. `1..=9` in a `match`) must be made of constants
+    fn visit_pat_mut(&mut self, _pat: &mut syn::Pat) {}
+
+    // Items of a view function (e.g. `const`) are evaluated at compile time
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_literals_with_lookups() {
+        let literals = Literals::extract(&quote!(text("hello").size(20)));
+
+        assert_eq!(
+            literals.view.to_string(),
+            quote!(
+                text(thawing_guest::literal::patch("hello", 0u32))
+                    .size(thawing_guest::literal::patch(20, 1u32))
+            )
+            .to_string()
+        );
+        assert!(matches!(
+            literals.values.as_slice(),
+            [Literal::Text(text), Literal::Integer(20)] if text == "hello"
+        ));
+    }
+
+    #[test]
+    fn keeps_the_shape_when_only_values_change() {
+        let before = Literals::extract(&quote!(text("hello").size(20)));
+        let after = Literals::extract(&quote!(text("world").size(30)));
+        let float = Literals::extract(&quote!(text("hello").size(20.0)));
+
+        assert_eq!(before.shape.to_string(), after.shape.to_string());
+        assert_ne!(before.shape.to_string(), float.shape.to_string());
+    }
+
+    #[test]
+    fn leaves_constants_untouched() {
+        let literals = Literals::extract(&quote! {
+            match self.value {
+                1..=9 => text(format!("{} digit", 1)),
+                _ => text([0; 3].len()),
+            }
+        });
+
+        assert!(matches!(literals.values.as_slice(), [Literal::Integer(0)]));
+    }
+}
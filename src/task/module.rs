@@ -0,0 +1,438 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+
+use crate::Config;
+
+/// A parsed source file of the caller's crate.
+pub struct Module {
+    pub path: PathBuf,
+    pub source: String,
+    pub file: syn::File,
+}
+
+/// The files a view depends on: the modules used by its file, along with
+/// the extra files matching [`Config::include`].
+#[derive(Debug, Clone)]
+pub struct Dependencies {
+    caller: PathBuf,
+    /// The directory of the caller's crate, which globs are relative to.
+    root: PathBuf,
+    /// The directory of the crate root module.
+    src: PathBuf,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    /// The directories of the files matching [`Config::include`], up to the
+    /// first component with a glob, and whether they are matched recursively.
+    bases: Vec<(PathBuf, bool)>,
+}
+
+impl Dependencies {
+    pub fn new(caller: &Path, config: &Config) -> Self {
+        let parent = caller.parent().unwrap_or(Path::new("/"));
+        let root = parent
+            .ancestors()
+            .find(|directory| directory.join("Cargo.toml").exists())
+            .unwrap_or(parent)
+            .to_path_buf();
+
+        // Files outside of `src`, like examples, are crate roots themselves
+        let src =
+            if caller.starts_with(root.join("src")) && !caller.starts_with(root.join("src/bin")) {
+                root.join("src")
+            } else {
+                parent.to_path_buf()
+            };
+
+        let patterns = |globs: &[String]| {
+            globs
+                .iter()
+                .filter_map(
+                    |glob| match Pattern::new(&root.join(glob).to_string_lossy()) {
+                        Ok(pattern) => Some(pattern),
+                        Err(error) => {
+                            tracing::warn!("Ignoring invalid glob {glob:?}: {error}");
+                            None
+                        }
+                    },
+                )
+                .collect::<Vec<_>>()
+        };
+
+        let bases = config
+            .include
+            .iter()
+            .map(|glob| {
+                let components: Vec<_> = Path::new(glob).components().collect();
+                let literal = components
+                    .iter()
+                    .position(|component| {
+                        component
+                            .as_os_str()
+                            .to_string_lossy()
+                            .contains(['*', '?', '[', '{'])
+                    })
+                    .unwrap_or(components.len());
+
+                let base = root.join(components[..literal].iter().collect::<PathBuf>());
+                let recursive = components.len() - literal > 1 || glob.contains("**");
+
+                (base, recursive)
+            })
+            .collect();
+
+        Self {
+            caller: caller.to_path_buf(),
+            include: patterns(&config.include),
+            exclude: patterns(&config.exclude),
+            bases,
+            root,
+            src,
+        }
+    }
+
+    /// The directories to watch for changes, and whether to watch them recursively.
+    ///
+    /// Only the modules and the extra files are watched, so builds writing
+    /// to the target directory are not noticed.
+    pub fn watched(&self) -> Vec<(PathBuf, bool)> {
+        // A crate without `src` keeps its modules next to its target directory
+        let target = self.root.join("target");
+        let mut watched = if target.starts_with(&self.src) {
+            let children = fs::read_dir(&self.src)
+                .into_iter()
+                .flatten()
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir() && *path != target)
+                .map(|path| (path, true));
+
+            [(self.src.clone(), false)]
+                .into_iter()
+                .chain(children)
+                .collect()
+        } else {
+            vec![(self.src.clone(), true)]
+        };
+
+        for (base, recursive) in &self.bases {
+            let covered = watched.iter().any(|(directory, watched)| {
+                base.starts_with(directory) && (*watched || base == directory && !recursive)
+            });
+
+            if !covered {
+                watched.push((base.clone(), *recursive));
+            }
+        }
+
+        watched
+    }
+
+    /// Parses the caller file and the modules it uses, through its `use crate::`,
+    /// `use super::` and `use self::` items, transitively.
+    ///
+    /// The caller file always comes first.
+    pub fn modules(&self) -> Result<Vec<Module>, crate::Error> {
+        let mut modules = Vec::new();
+        let mut visited = HashSet::from([self.caller.clone()]);
+        let mut queue = vec![self.caller.clone()];
+
+        while !queue.is_empty() {
+            let path = queue.remove(0);
+            let source = fs::read_to_string(&path)?;
+            let file = syn::parse_file(&source)?;
+
+            for dependency in self.uses(&path, &file) {
+                if !self.is_excluded(&dependency) && visited.insert(dependency.clone()) {
+                    queue.push(dependency);
+                }
+            }
+
+            modules.push(Module { path, source, file });
+        }
+
+        Ok(modules)
+    }
+
+    /// The extra files matching [`Config::include`], sorted.
+    pub fn extras(&self) -> Vec<PathBuf> {
+        let mut extras: Vec<_> = self
+            .include
+            .iter()
+            .filter_map(|pattern| glob::glob(pattern.as_str()).ok())
+            .flatten()
+            .filter_map(Result::ok)
+            .filter(|path| path.is_file() && !self.is_excluded(path))
+            .collect();
+
+        extras.sort();
+        extras.dedup();
+        extras
+    }
+
+    /// Whether a change to any of the files at `paths` may affect the view.
+    pub fn is_affected_by<'a>(&self, paths: impl IntoIterator<Item = &'a Path>) -> bool {
+        let mut sources = Vec::new();
+
+        for path in paths {
+            if self.is_excluded(path) {
+                continue;
+            }
+
+            if self
+                .include
+                .iter()
+                .any(|pattern| pattern.matches_path(path))
+            {
+                return true;
+            }
+
+            if path.extension().is_some_and(|extension| extension == "rs") {
+                sources.push(path);
+            }
+        }
+
+        if sources.is_empty() {
+            return false;
+        }
+
+        // Resolved again, since the changes may have added or removed modules
+        self.modules()
+            .map(|modules| {
+                modules
+                    .iter()
+                    .any(|module| sources.contains(&module.path.as_path()))
+            })
+            // A broken module may still be one of them, so the error can be shown
+            .unwrap_or(true)
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude
+            .iter()
+            .any(|pattern| pattern.matches_path(path))
+    }
+
+    /// Resolves the files of the modules used by the file at `path`.
+    fn uses(&self, path: &Path, file: &syn::File) -> Vec<PathBuf> {
+        let current = self.module_path(path);
+
+        // Out-of-line modules declared in this file can be used without `self::`
+        let children: Vec<_> = file
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                syn::Item::Mod(module) if module.content.is_none() => {
+                    Some(module.ident.to_string())
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut paths = Vec::new();
+        for item in &file.items {
+            if let syn::Item::Use(node) = item {
+                flatten(&node.tree, Vec::new(), &mut paths);
+            }
+        }
+
+        paths
+            .into_iter()
+            .filter_map(|segments| {
+                let mut segments = segments.into_iter().peekable();
+                let mut absolute = match segments.peek().map(String::as_str) {
+                    Some("crate") => {
+                        let _ = segments.next();
+                        Vec::new()
+                    }
+                    Some("self") => {
+                        let _ = segments.next();
+                        current.clone()
+                    }
+                    Some("super") => {
+                        let mut absolute = current.clone();
+                        while segments.next_if(|segment| segment == "super").is_some() {
+                            let _ = absolute.pop();
+                        }
+                        absolute
+                    }
+                    Some(first) if children.iter().any(|child| child == first) => current.clone(),
+                    _ => return None,
+                };
+                absolute.extend(segments);
+
+                // The longest prefix naming a file is the module of the used item
+                (0..=absolute.len())
+                    .rev()
+                    .find_map(|length| self.module_file(&absolute[..length]))
+            })
+            .filter(|dependency| dependency != path)
+            .collect()
+    }
+
+    /// The module path of the file at `path`, relative to the crate root.
+    fn module_path(&self, path: &Path) -> Vec<String> {
+        let Ok(relative) = path.strip_prefix(&self.src) else {
+            return Vec::new();
+        };
+
+        let mut segments: Vec<_> = relative
+            .with_extension("")
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+
+        if segments.last().is_some_and(|last| last == "mod") {
+            let _ = segments.pop();
+        }
+
+        if segments.len() == 1 && (segments[0] == "main" || segments[0] == "lib") {
+            segments.clear();
+        }
+
+        if path == self.caller && self.src != self.root.join("src") {
+            segments.clear();
+        }
+
+        segments
+    }
+
+    /// The file of the module at the given path, if it exists.
+    fn module_file(&self, segments: &[String]) -> Option<PathBuf> {
+        if segments.is_empty() {
+            if self.src != self.root.join("src") {
+                return Some(self.caller.clone());
+            }
+
+            return ["main.rs", "lib.rs"]
+                .into_iter()
+                .map(|root| self.src.join(root))
+                .find(|root| root.exists());
+        }
+
+        let module = segments.iter().collect::<PathBuf>();
+
+        [module.with_extension("rs"), module.join("mod.rs")]
+            .into_iter()
+            .map(|candidate| self.src.join(candidate))
+            .find(|candidate| candidate.exists())
+    }
+}
+
+/// Collects every path imported by a `use` tree.
+fn flatten(tree: &syn::UseTree, prefix: Vec<String>, paths: &mut Vec<Vec<String>>) {
+    match tree {
+        syn::UseTree::Path(path) => {
+            let mut prefix = prefix;
+            prefix.push(path.ident.to_string());
+            flatten(&path.tree, prefix, paths);
+        }
+        syn::UseTree::Name(name) => {
+            let mut path = prefix;
+            path.push(name.ident.to_string());
+            paths.push(path);
+        }
+        syn::UseTree::Rename(rename) => {
+            let mut path = prefix;
+            path.push(rename.ident.to_string());
+            paths.push(path);
+        }
+        syn::UseTree::Glob(_) => paths.push(prefix),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten(tree, prefix.clone(), paths);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a crate with the given files in a new temporary directory.
+    fn create(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("thawing-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        for (path, contents) in [("Cargo.toml", "")].iter().chain(files) {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        root
+    }
+
+    fn uses(dependencies: &Dependencies, path: &Path) -> Vec<PathBuf> {
+        let file = syn::parse_file(&fs::read_to_string(path).unwrap()).unwrap();
+
+        dependencies.uses(path, &file)
+    }
+
+    #[test]
+    fn resolves_the_used_modules() {
+        let root = create(
+            "uses",
+            &[
+                (
+                    "src/main.rs",
+                    "mod state;\nmod view;\nuse state::Counter;\nuse iced::Element;\n",
+                ),
+                ("src/state.rs", "pub struct Counter;\n"),
+                (
+                    "src/view/mod.rs",
+                    "mod helpers;\nuse super::state::Counter;\nuse self::helpers::label;\n",
+                ),
+                (
+                    "src/view/helpers.rs",
+                    "use crate::state::*;\nuse super::helpers::title;\n",
+                ),
+            ],
+        );
+        let src = root.join("src");
+        let dependencies = Dependencies::new(&src.join("main.rs"), &Config::default());
+
+        assert_eq!(
+            uses(&dependencies, &src.join("main.rs")),
+            [src.join("state.rs")]
+        );
+        assert_eq!(
+            uses(&dependencies, &src.join("view/mod.rs")),
+            [src.join("state.rs"), src.join("view/helpers.rs")]
+        );
+        assert_eq!(
+            uses(&dependencies, &src.join("view/helpers.rs")),
+            [src.join("state.rs")]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn resolves_the_modules_of_an_example_from_its_file() {
+        let root = create(
+            "example",
+            &[
+                ("src/lib.rs", "pub mod widget;\n"),
+                ("src/widget.rs", ""),
+                (
+                    "examples/counter.rs",
+                    "mod theme;\nuse crate::theme::palette;\n",
+                ),
+                ("examples/theme.rs", "pub fn palette() {}\n"),
+            ],
+        );
+        let caller = root.join("examples/counter.rs");
+        let dependencies = Dependencies::new(&caller, &Config::default());
+
+        assert_eq!(
+            uses(&dependencies, &caller),
+            [root.join("examples/theme.rs")]
+        );
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
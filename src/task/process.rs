@@ -0,0 +1,131 @@
+use std::io;
+use std::process::{Child, Command, ExitStatus};
+
+/// A child process along with everything it spawns, e.g. the `rustc`s of `cargo`,
+/// so they can be killed together.
+///
+/// The group is left out of the one of the terminal, so it is killed when
+/// dropped while still running, e.g. when the build thread unwinds.
+pub struct Group {
+    child: Child,
+    #[cfg(windows)]
+    job: windows::Job,
+}
+
+impl Group {
+    pub fn spawn(command: &mut Command) -> io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+
+            let _ = command.process_group(0);
+        }
+
+        let child = command.spawn()?;
+
+        #[cfg(windows)]
+        let job = match windows::Job::assign(&child) {
+            Ok(job) => job,
+            Err(error) => {
+                let mut child = child;
+                let _ = child.kill();
+                let _ = child.wait();
+
+                return Err(error);
+            }
+        };
+
+        Ok(Self {
+            child,
+            #[cfg(windows)]
+            job,
+        })
+    }
+
+    pub fn child(&mut self) -> &mut Child {
+        &mut self.child
+    }
+
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.child.try_wait()
+    }
+
+    /// Kills the whole group, and waits for the child.
+    pub fn kill(mut self) {
+        self.terminate();
+    }
+
+    fn terminate(&mut self) {
+        #[cfg(unix)]
+        // SAFETY: `killpg` has no memory safety requirements, and the group was
+        // created with the id of the child, which is not reaped until `wait` below.
+        let _ = unsafe { libc::killpg(self.child.id() as libc::pid_t, libc::SIGKILL) };
+
+        #[cfg(windows)]
+        self.job.terminate();
+
+        #[cfg(not(any(unix, windows)))]
+        let _ = self.child.kill();
+
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for Group {
+    fn drop(&mut self) {
+        if let Ok(None) = self.child.try_wait() {
+            self.terminate();
+        }
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::io;
+    use std::os::windows::io::AsRawHandle;
+    use std::process::Child;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, TerminateJobObject,
+    };
+
+    /// A job object holding the child, which its own children are added to.
+    pub struct Job(HANDLE);
+
+    impl Job {
+        pub fn assign(child: &Child) -> io::Result<Self> {
+            // SAFETY: both arguments may be null, creating an unnamed job
+            // with the default security attributes.
+            let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+            if handle.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+
+            let job = Self(handle);
+
+            // SAFETY: the job was just created, and the handle of the child
+            // stays valid while it is borrowed.
+            if unsafe { AssignProcessToJobObject(job.0, child.as_raw_handle() as HANDLE) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(job)
+        }
+
+        pub fn terminate(&self) {
+            // SAFETY: the handle is owned by `self`, and is only closed on drop.
+            let _ = unsafe { TerminateJobObject(self.0, 1) };
+        }
+    }
+
+    impl Drop for Job {
+        fn drop(&mut self) {
+            // SAFETY: the handle is owned by `self`, and is not used afterwards.
+            let _ = unsafe { CloseHandle(self.0) };
+        }
+    }
+
+    // SAFETY: a job handle can be used from any thread.
+    unsafe impl Send for Job {}
+}
@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use proc_macro2::{Delimiter, LineColumn, TokenStream, TokenTree};
+
+use crate::error::{Location, Severity};
+
+/// How far ahead in the generated file to look for an original token,
+/// before assuming it was dropped by the pretty printer.
+const LOOKAHEAD: usize = 16;
+
+/// How many leading tokens of a chunk must match to find where it starts.
+const ANCHOR: usize = 8;
+
+/// Maps positions in the generated `lib.rs` back to the files the code was
+/// extracted from, by aligning the tokens of every extracted chunk with the
+/// tokens of the generated output.
+#[derive(Debug, Default)]
+pub struct SpanMap {
+    files: Vec<(PathBuf, String)>,
+    /// The position of a generated token, and the file and position it came from.
+    entries: Vec<(LineColumn, usize, LineColumn)>,
+    ranges: Vec<(LineColumn, LineColumn)>,
+}
+
+impl SpanMap {
+    /// Creates a [`SpanMap`] from the source files, and the chunks extracted
+    /// from them in the order they were generated, along with the index of their file.
+    pub fn new(
+        files: Vec<(PathBuf, String)>,
+        chunks: &[(usize, &TokenStream)],
+        generated: &str,
+    ) -> Self {
+        let generated = TokenStream::from_str(generated)
+            .map(leaves)
+            .unwrap_or_default();
+
+        let mut entries = Vec::new();
+        let mut ranges = Vec::new();
+        let mut cursor = 0;
+
+        for (file, chunk) in chunks {
+            let original = leaves((*chunk).clone());
+            let start = entries.len();
+
+            if original.is_empty() {
+                continue;
+            }
+
+            let anchor: Vec<_> = original
+                .iter()
+                .take(ANCHOR)
+                .map(|(token, _)| token)
+                .collect();
+            if let Some(offset) = generated[cursor..]
+                .windows(anchor.len())
+                .position(|window| {
+                    window
+                        .iter()
+                        .zip(&anchor)
+                        .all(|((generated, _), token)| generated == *token)
+                })
+            {
+                cursor += offset;
+            }
+
+            for (token, position) in original {
+                let Some(offset) = generated[cursor..]
+                    .iter()
+                    .take(LOOKAHEAD)
+                    .position(|(generated, _)| *generated == token)
+                else {
+                    continue;
+                };
+
+                entries.push((generated[cursor + offset].1, *file, position));
+                cursor += offset + 1;
+            }
+
+            if let (Some(first), Some(last)) = (entries.get(start), entries.last()) {
+                ranges.push((first.0, last.0));
+            }
+        }
+
+        Self {
+            files,
+            entries,
+            ranges,
+        }
+    }
+
+    /// Locates a 1-based line and column of the generated file in its original file.
+    pub fn locate(&self, line: usize, column: usize) -> Option<Location> {
+        let position = LineColumn {
+            line,
+            column: column.saturating_sub(1),
+        };
+        let key = |position: LineColumn| (position.line, position.column);
+
+        self.ranges
+            .iter()
+            .find(|(start, end)| key(*start) <= key(position) && key(position) <= key(*end))?;
+
+        let (generated, file, original) = self
+            .entries
+            .iter()
+            .take_while(|(generated, _, _)| key(*generated) <= key(position))
+            .last()?;
+
+        let column = if generated.line == position.line {
+            original.column + (position.column - generated.column)
+        } else {
+            original.column
+        };
+
+        Some(Location {
+            file: self.files.get(*file)?.0.clone(),
+            line: original.line,
+            column: column + 1,
+        })
+    }
+
+    /// Renders a diagnostic pointing at a location of one of the original files.
+    pub fn render(&self, severity: Severity, message: &str, location: &Location) -> String {
+        let label = match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+            Severity::Help => "help",
+        };
+
+        let line = self
+            .files
+            .iter()
+            .find(|(path, _)| *path == location.file)
+            .map(|(_, source)| source.as_str())
+            .unwrap_or_default()
+            .lines()
+            .nth(location.line.saturating_sub(1))
+            .unwrap_or_default();
+        let number = location.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let caret = " ".repeat(location.column.saturating_sub(1));
+
+        format!(
+            "{label}: {message}\n{gutter}--> {location}\n{gutter} |\n{number} | {line}\n{gutter} | {caret}^\n"
+        )
+    }
+}
+
+/// Flattens a [`TokenStream`], keeping the starting position of every token.
+///
+/// Literals are compared by kind only, since the pretty printer may
+/// rewrite them (e.g. doc comments).
+fn leaves(stream: TokenStream) -> Vec<(String, LineColumn)> {
+    let mut leaves = Vec::new();
+
+    for tree in stream {
+        match tree {
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+
+                if !open.is_empty() {
+                    leaves.push((open.to_string(), group.span_open().start()));
+                }
+
+                leaves.extend(self::leaves(group.stream()));
+
+                if !close.is_empty() {
+                    leaves.push((close.to_string(), group.span_close().start()));
+                }
+            }
+            TokenTree::Ident(ident) => leaves.push((ident.to_string(), ident.span().start())),
+            TokenTree::Punct(punct) => {
+                leaves.push((punct.as_char().to_string(), punct.span().start()))
+            }
+            TokenTree::Literal(literal) => {
+                leaves.push((String::from("<literal>"), literal.span().start()))
+            }
+        }
+    }
+
+    leaves
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(source: &str) -> TokenStream {
+        TokenStream::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn locates_tokens_in_the_original_file() {
+        let source = "fn view() {\n    text(\"hello\").size(20)\n}\n";
+        let generated =
+            "use thawing_guest::prelude::*;\n\nfn view() {\n        text(\"hello\").size(20)\n}\n";
+
+        let view = chunk(source);
+        let map = SpanMap::new(
+            vec![(PathBuf::from("src/main.rs"), source.to_owned())],
+            &[(0, &view)],
+            generated,
+        );
+
+        assert_eq!(
+            map.locate(4, 23),
+            Some(Location {
+                file: PathBuf::from("src/main.rs"),
+                line: 2,
+                column: 19,
+            })
+        );
+
+        // Inside of a token, the offset from its start is kept
+        assert_eq!(map.locate(4, 25).map(|location| location.column), Some(21));
+    }
+
+    #[test]
+    fn ignores_generated_code() {
+        let source = "fn view() {\n    text(\"hello\")\n}\n";
+        let generated = "use thawing_guest::prelude::*;\n\nfn view() {\n    text(\"hello\")\n}\n\nthawing_guest::thaw!(State);\n";
+
+        let view = chunk(source);
+        let map = SpanMap::new(
+            vec![(PathBuf::from("src/main.rs"), source.to_owned())],
+            &[(0, &view)],
+            generated,
+        );
+
+        assert_eq!(map.locate(1, 5), None);
+        assert_eq!(map.locate(7, 1), None);
+    }
+
+    #[test]
+    fn locates_chunks_of_several_files() {
+        let view = "fn view() {\n    text(helper())\n}\n";
+        let helper = "\n\nfn helper() -> u32 {\n    42\n}\n";
+        let generated = "fn view() {\n    text(helper())\n}\nfn helper() -> u32 {\n    42\n}\n";
+
+        let (first, second) = (chunk(view), chunk(helper));
+        let map = SpanMap::new(
+            vec![
+                (PathBuf::from("src/main.rs"), view.to_owned()),
+                (PathBuf::from("src/helper.rs"), helper.to_owned()),
+            ],
+            &[(0, &first), (1, &second)],
+            generated,
+        );
+
+        assert_eq!(
+            map.locate(2, 10),
+            Some(Location {
+                file: PathBuf::from("src/main.rs"),
+                line: 2,
+                column: 10,
+            })
+        );
+        assert_eq!(
+            map.locate(5, 5),
+            Some(Location {
+                file: PathBuf::from("src/helper.rs"),
+                line: 4,
+                column: 5,
+            })
+        );
+    }
+}
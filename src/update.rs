@@ -0,0 +1,105 @@
+#[cfg(feature = "hot")]
+use std::cell::RefCell;
+#[cfg(feature = "hot")]
+use std::collections::HashMap;
+#[cfg(feature = "hot")]
+use std::sync::Arc;
+
+#[cfg(feature = "hot")]
+use crate::codec::Codec;
+#[cfg(feature = "hot")]
+use crate::runtime;
+use crate::widget::Id;
+
+#[cfg(feature = "hot")]
+thread_local! {
+    /// The components loaded by the widgets with an [`Id`].
+    ///
+    /// Kept per thread, since they hold elements, and `update` runs on the
+    /// same thread as the widgets.
+    static COMPONENTS: RefCell<HashMap<Id, runtime::State<'static>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Applies the message to the state with the update logic of the component
+/// loaded by the widget with the given [`Id`], as done by `#[thawing::update]`.
+///
+/// Returns `false` if the message was not applied, since the component is not
+/// loaded yet, has no update logic, or failed; so it can be applied natively.
+#[cfg(feature = "hot")]
+pub fn update<State, Message>(id: impl Into<Id>, state: &mut State, message: &Message) -> bool
+where
+    State: serde::Serialize + serde::de::DeserializeOwned,
+    Message: serde::Serialize,
+{
+    let id = id.into();
+    let Some(component) = COMPONENTS.with_borrow(|components| components.get(&id).cloned()) else {
+        return false;
+    };
+
+    if !component.has_update {
+        return false;
+    }
+
+    match apply(&component, state, message) {
+        Ok(Some(updated)) => {
+            *state = updated;
+            true
+        }
+        Ok(None) => false,
+        Err(error) => {
+            tracing::error!("Failed to update with the component, updating natively: {error}");
+            false
+        }
+    }
+}
+
+#[cfg(feature = "hot")]
+fn apply<State, Message>(
+    component: &runtime::State<'static>,
+    state: &State,
+    message: &Message,
+) -> Result<Option<State>, crate::Error>
+where
+    State: serde::Serialize + serde::de::DeserializeOwned,
+    Message: serde::Serialize,
+{
+    let codec = component.codec;
+    let state = codec.encode(state)?;
+    let message = codec.encode(message)?;
+
+    let Some(updated) = component.update(&state, &message)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(codec.decode(&updated)?))
+}
+
+/// Makes the current component of the widget available to [`update`].
+#[cfg(feature = "hot")]
+pub(crate) fn register(id: &Id, component: runtime::State<'static>) {
+    COMPONENTS.with_borrow_mut(|components| {
+        let _ = components.insert(id.clone(), component);
+    });
+}
+
+/// Forgets the component of the widget, unless it was replaced already.
+#[cfg(feature = "hot")]
+pub(crate) fn unregister(id: &Id, component: &runtime::State<'static>) {
+    COMPONENTS.with_borrow_mut(|components| {
+        if components
+            .get(id)
+            .is_some_and(|current| Arc::ptr_eq(&current.store, &component.store))
+        {
+            let _ = components.remove(id);
+        }
+    });
+}
+
+/// Never applies the message, since there is no component to apply it with
+/// without the `hot` feature.
+#[cfg(not(feature = "hot"))]
+#[inline(always)]
+pub fn update<State, Message>(_id: impl Into<Id>, _state: &mut State, _message: &Message) -> bool {
+    false
+}
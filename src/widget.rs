@@ -1,40 +1,70 @@
+mod filter;
 mod id;
+mod overlay;
+#[cfg(feature = "hot")]
+mod panel;
+#[cfg(not(feature = "hot"))]
+mod passthrough;
+#[cfg(feature = "hot")]
 mod state;
 
-use std::marker::PhantomData;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "hot")]
+use std::path::Path;
+#[cfg(feature = "hot")]
 use std::sync::Arc;
 
+#[cfg(feature = "hot")]
 use iced_core::widget::{Operation, Tree, tree};
+#[cfg(feature = "hot")]
 use iced_core::{Clipboard, Event, Layout, Length, Rectangle, Shell, Size, Widget};
+#[cfg(feature = "hot")]
 use iced_core::{layout, mouse, renderer};
 
+#[cfg(feature = "hot")]
 use crate::Element;
+#[cfg(feature = "hot")]
+use crate::codec::{self, Codec};
+#[cfg(feature = "hot")]
+use crate::task::Caller;
+pub(crate) use filter::Filter;
 pub use id::Id;
-pub(crate) use state::{Error, State};
+#[cfg(not(feature = "hot"))]
+pub use passthrough::Thawing;
+#[cfg(feature = "hot")]
+pub(crate) use state::State;
 
+#[cfg(feature = "hot")]
 pub struct Thawing<'a, Message, Data = ()> {
     id: Option<Id>,
     width: Length,
     height: Length,
 
-    caller: Result<PathBuf, crate::Error>,
-    bytes: Result<Arc<Vec<u8>>, crate::Error>,
+    caller: Result<Caller, crate::Error>,
+    /// The state given to the guest, encoded once the codec of the
+    /// runtime is known.
+    data: Option<&'a Data>,
 
     initial: Element<'a, Message>,
-    state: PhantomData<&'a Data>,
 }
 
+#[cfg(feature = "hot")]
 impl<'a, Message, Data> Thawing<'a, Message, Data> {
-    pub fn from_view(element: impl Into<Element<'a, Message>>, file: &'static str) -> Self {
+    pub fn from_view(
+        element: impl Into<Element<'a, Message>>,
+        file: &'static str,
+        line: u32,
+        column: u32,
+    ) -> Self {
         Self {
             id: None,
-            caller: Path::new(file).canonicalize().map_err(crate::Error::from),
+            caller: Path::new(file)
+                .canonicalize()
+                .map(|path| Caller { path, line, column })
+                .map_err(crate::Error::from),
             initial: element.into(),
-            bytes: Ok(Arc::new(Vec::new())),
+            data: None,
             width: Length::Shrink,
             height: Length::Shrink,
-            state: PhantomData,
         }
     }
 
@@ -44,18 +74,25 @@ impl<'a, Message, Data> Thawing<'a, Message, Data> {
     }
 }
 
+#[cfg(feature = "hot")]
 impl<'a, Message, Data> Thawing<'a, Message, Data>
 where
     Data: serde::Serialize,
 {
-    pub fn state<'b>(mut self, state: &'b Data) -> Self {
-        self.bytes = bincode::serialize(state)
-            .map(Arc::new)
-            .map_err(crate::Error::from);
+    pub fn state(mut self, state: &'a Data) -> Self {
+        self.data = Some(state);
         self
     }
+
+    fn encode(&self, codec: codec::Format) -> Result<Arc<Vec<u8>>, crate::Error> {
+        match self.data {
+            Some(data) => codec.encode(data).map(Arc::new),
+            None => Ok(Arc::new(Vec::new())),
+        }
+    }
 }
 
+#[cfg(feature = "hot")]
 impl<'a, Message, Data> From<Thawing<'a, Message, Data>> for Element<'a, Message>
 where
     Data: serde::Serialize + 'static,
@@ -66,6 +103,7 @@ where
     }
 }
 
+#[cfg(feature = "hot")]
 impl<'a, Message, Data> Widget<Message, iced_widget::Theme, iced_widget::Renderer>
     for Thawing<'a, Message, Data>
 where
@@ -79,7 +117,8 @@ where
     }
 
     fn state(&self) -> tree::State {
-        let state = State::<Message>::new(&self.bytes, &self.caller);
+        let codec = codec::of(self.id.as_ref());
+        let state = State::<Message>::new(&self.id, &self.encode(codec), codec, &self.caller);
         tree::State::new(state)
     }
 
@@ -89,8 +128,9 @@ where
 
     fn diff(&self, tree: &mut Tree) {
         let state = tree.state.downcast_mut::<State<Message>>();
+        let bytes = self.encode(state.codec(self.id.as_ref()));
 
-        state.diff(&self.bytes, &self.initial, &mut tree.children[0]);
+        state.diff(&bytes, &self.initial, &mut tree.children[0]);
     }
 
     fn size(&self) -> Size<Length> {
@@ -212,6 +252,7 @@ where
         let state = tree.state.downcast_mut::<State<Message>>();
 
         state.overlay(
+            self.id.as_ref(),
             &mut self.initial,
             &mut tree.children[0],
             layout,
@@ -0,0 +1,230 @@
+use iced_core::widget::{Operation, Tree, tree};
+use iced_core::{Clipboard, Event, Layout, Length, Rectangle, Shell, Size, Vector, Widget};
+use iced_core::{layout, mouse, overlay, renderer};
+use iced_widget::{Renderer, Theme};
+
+use crate::Element;
+use crate::widget::overlay::merge;
+
+/// Maps the messages of an element like [`Element::map`], but drops the ones
+/// the mapper returns nothing for.
+pub struct Filter<'a, A, B> {
+    content: Element<'a, A>,
+    mapper: Box<dyn Fn(A) -> Option<B> + 'a>,
+}
+
+impl<'a, A: 'a, B: 'a> Filter<'a, A, B> {
+    pub fn new(content: Element<'a, A>, mapper: impl Fn(A) -> Option<B> + 'a) -> Element<'a, B> {
+        Element::new(Self {
+            content,
+            mapper: Box::new(mapper),
+        })
+    }
+}
+
+impl<A, B> Widget<B, Theme, Renderer> for Filter<'_, A, B> {
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn size_hint(&self) -> Size<Length> {
+        self.content.as_widget().size_hint()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        tree: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, B>,
+        viewport: &Rectangle,
+    ) {
+        let mut messages = Vec::new();
+        let mut local = Shell::new(&mut messages);
+
+        self.content.as_widget_mut().update(
+            tree, event, layout, cursor, renderer, clipboard, &mut local, viewport,
+        );
+
+        merge(shell, &local);
+
+        for message in messages.into_iter().filter_map(&self.mapper) {
+            shell.publish(message);
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+        viewport: &Rectangle,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, B, Theme, Renderer>> {
+        let mapper = &self.mapper;
+
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, viewport, translation)
+            .map(move |content| Overlay::new(content, mapper))
+    }
+}
+
+/// An overlay of a [`Filter`], whose messages are mapped the same way.
+struct Overlay<'a, A, B> {
+    content: overlay::Element<'a, A, Theme, Renderer>,
+    mapper: &'a dyn Fn(A) -> Option<B>,
+}
+
+impl<'a, A: 'a, B: 'a> Overlay<'a, A, B> {
+    fn new(
+        content: overlay::Element<'a, A, Theme, Renderer>,
+        mapper: &'a dyn Fn(A) -> Option<B>,
+    ) -> overlay::Element<'a, B, Theme, Renderer> {
+        overlay::Element::new(Box::new(Self { content, mapper }))
+    }
+}
+
+impl<A, B> overlay::Overlay<B, Theme, Renderer> for Overlay<'_, A, B> {
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        self.content.as_overlay_mut().layout(renderer, bounds)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.content
+            .as_overlay()
+            .draw(renderer, theme, style, layout, cursor);
+    }
+
+    fn operate(&mut self, layout: Layout<'_>, renderer: &Renderer, operation: &mut dyn Operation) {
+        self.content
+            .as_overlay_mut()
+            .operate(layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, B>,
+    ) {
+        let mut messages = Vec::new();
+        let mut local = Shell::new(&mut messages);
+
+        self.content
+            .as_overlay_mut()
+            .update(event, layout, cursor, renderer, clipboard, &mut local);
+
+        merge(shell, &local);
+
+        for message in messages.into_iter().filter_map(self.mapper) {
+            shell.publish(message);
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_overlay()
+            .mouse_interaction(layout, cursor, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, B, Theme, Renderer>> {
+        let mapper = self.mapper;
+
+        self.content
+            .as_overlay_mut()
+            .overlay(layout, renderer)
+            .map(move |content| Overlay::new(content, mapper))
+    }
+
+    fn index(&self) -> f32 {
+        self.content.as_overlay().index()
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(feature = "hot")]
+use std::cell::RefCell;
+
+use iced_core::Shell;
+#[cfg(feature = "hot")]
+use iced_core::{Clipboard, Event, Layout, Size};
+#[cfg(feature = "hot")]
+use iced_core::{layout, mouse, overlay, renderer, widget};
+#[cfg(feature = "hot")]
+use iced_widget::{Renderer, Theme};
+
+#[cfg(feature = "hot")]
+use crate::{guest, runtime};
+
+/// An overlay of the guest element, whose messages are mapped through
+/// the runtime like the ones of the element itself.
+#[cfg(feature = "hot")]
+pub struct Guest<'a> {
+    content: overlay::Element<'a, guest::Message, Theme, Renderer>,
+    runtime: &'a runtime::State<'static>,
+    /// Where a failure to map a message is kept, until the widget shows it.
+    failure: &'a RefCell<Option<crate::Error>>,
+}
+
+#[cfg(feature = "hot")]
+impl<'a> Guest<'a> {
+    pub fn new<Message: serde::de::DeserializeOwned>(
+        content: overlay::Element<'a, guest::Message, Theme, Renderer>,
+        runtime: &'a runtime::State<'static>,
+        failure: &'a RefCell<Option<crate::Error>>,
+    ) -> overlay::Element<'a, Message, Theme, Renderer> {
+        overlay::Element::new(Box::new(Self {
+            content,
+            runtime,
+            failure,
+        }))
+    }
+}
+
+#[cfg(feature = "hot")]
+impl<Message: serde::de::DeserializeOwned> overlay::Overlay<Message, Theme, Renderer>
+    for Guest<'_>
+{
+    fn layout(&mut self, renderer: &Renderer, bounds: Size) -> layout::Node {
+        self.content.as_overlay_mut().layout(renderer, bounds)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.content
+            .as_overlay()
+            .draw(renderer, theme, style, layout, cursor);
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn widget::Operation,
+    ) {
+        self.content
+            .as_overlay_mut()
+            .operate(layout, renderer, operation);
+    }
+
+    fn update(
+        &mut self,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let mut messages = Vec::new();
+        let mut guest = Shell::new(&mut messages);
+
+        self.content
+            .as_overlay_mut()
+            .update(event, layout, cursor, renderer, clipboard, &mut guest);
+
+        merge(shell, &guest);
+
+        if let Err(error) = relay(self.runtime, messages, shell) {
+            *self.failure.borrow_mut() = Some(error);
+            shell.request_redraw();
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_overlay()
+            .mouse_interaction(layout, cursor, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        layout: Layout<'b>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Theme, Renderer>> {
+        let overlay = self.content.as_overlay_mut().overlay(layout, renderer)?;
+
+        Some(Guest::new(overlay, self.runtime, self.failure))
+    }
+
+    fn index(&self) -> f32 {
+        self.content.as_overlay().index()
+    }
+}
+
+/// Merges the requests of a shell of the guest into the one of the host,
+/// leaving out its messages.
+pub fn merge<A, B>(shell: &mut Shell<'_, A>, guest: &Shell<'_, B>) {
+    if guest.is_event_captured() {
+        shell.capture_event();
+    }
+
+    if guest.is_layout_invalid() {
+        shell.invalidate_layout();
+    }
+
+    if guest.are_widgets_invalid() {
+        shell.invalidate_widgets();
+    }
+
+    shell.request_redraw_at(guest.redraw_request());
+}
+
+/// Maps the messages of the guest through its closures, publishing them until
+/// one fails.
+#[cfg(feature = "hot")]
+pub fn relay<Message: serde::de::DeserializeOwned>(
+    runtime: &runtime::State<'static>,
+    messages: Vec<guest::Message>,
+    shell: &mut Shell<'_, Message>,
+) -> Result<(), crate::Error> {
+    for message in messages {
+        shell.publish(runtime.call(message.closure, message.data.transpose()?)?);
+    }
+
+    Ok(())
+}
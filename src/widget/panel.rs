@@ -0,0 +1,279 @@
+use iced_core::widget::Tree;
+use iced_core::{Alignment, Border, Color, Event, Font, Layout, Length, Point, Rectangle, Shell};
+use iced_core::{Clipboard, Size, Vector};
+use iced_core::{clipboard, layout, mouse, overlay, renderer};
+use iced_widget::{Renderer, Theme};
+use iced_widget::{button, column, container, row, scrollable, text};
+
+use crate::Element;
+use crate::error::Severity;
+use crate::widget::Id;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Message {
+    Toggle,
+    Copy,
+    Retry,
+}
+
+/// The error panel drawn on top of the guest view when a reload fails.
+///
+/// While collapsed, it is only a banner and the view below stays interactive.
+pub struct Panel {
+    error: crate::Error,
+    collapsed: bool,
+    element: Element<'static, Message>,
+    tree: Tree,
+}
+
+impl Panel {
+    pub fn new(error: crate::Error, collapsed: bool) -> Self {
+        let element = view(&error, collapsed);
+        let tree = Tree::new(&element);
+
+        Self {
+            error,
+            collapsed,
+            element,
+            tree,
+        }
+    }
+
+    pub fn overlay<'a, M>(
+        &'a mut self,
+        id: Option<&Id>,
+        bounds: Rectangle,
+        translation: Vector,
+    ) -> overlay::Element<'a, M, Theme, Renderer> {
+        overlay::Element::new(Box::new(Overlay {
+            panel: self,
+            id: id.cloned(),
+            bounds: bounds + translation,
+        }))
+    }
+
+    fn update(&mut self, message: Message, id: Option<&Id>, clipboard: &mut dyn Clipboard) {
+        match message {
+            Message::Toggle => {
+                self.collapsed = !self.collapsed;
+                self.element = view(&self.error, self.collapsed);
+                self.tree.diff(&self.element);
+            }
+            Message::Copy => clipboard.write(clipboard::Kind::Standard, report(&self.error)),
+            Message::Retry => match id {
+                Some(id) => crate::task::retry(id),
+                None => tracing::warn!("Cannot retry a `Thawing` widget without an `Id`"),
+            },
+        }
+    }
+}
+
+struct Overlay<'a> {
+    panel: &'a mut Panel,
+    id: Option<Id>,
+    bounds: Rectangle,
+}
+
+impl<'a, Message> overlay::Overlay<Message, Theme, Renderer> for Overlay<'a> {
+    fn layout(&mut self, renderer: &Renderer, _bounds: Size) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, self.bounds.size()).width(Length::Fill);
+        let node = self
+            .panel
+            .element
+            .as_widget()
+            .layout(&mut self.panel.tree, renderer, &limits);
+        let height = node.size().height;
+
+        node.move_to(Point::new(
+            self.bounds.x,
+            self.bounds.y + self.bounds.height - height,
+        ))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+    ) {
+        self.panel.element.as_widget().draw(
+            &self.panel.tree,
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            &layout.bounds(),
+        );
+    }
+
+    fn update(
+        &mut self,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let mut messages = Vec::new();
+        let mut panel = Shell::new(&mut messages);
+
+        self.panel.element.as_widget_mut().update(
+            &mut self.panel.tree,
+            event,
+            layout,
+            cursor,
+            renderer,
+            clipboard,
+            &mut panel,
+            &layout.bounds(),
+        );
+
+        if panel.is_event_captured() {
+            shell.capture_event();
+        }
+
+        if panel.is_layout_invalid() {
+            shell.invalidate_layout();
+        }
+
+        shell.request_redraw_at(panel.redraw_request());
+
+        for message in messages {
+            self.panel.update(message, self.id.as_ref(), clipboard);
+            shell.invalidate_layout();
+            shell.request_redraw();
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.panel.element.as_widget().mouse_interaction(
+            &self.panel.tree,
+            layout,
+            cursor,
+            &layout.bounds(),
+            renderer,
+        )
+    }
+}
+
+fn view(error: &crate::Error, collapsed: bool) -> Element<'static, Message> {
+    let diagnostics = diagnostics(error);
+    let errors = diagnostics
+        .iter()
+        .filter(|(severity, _)| *severity == Severity::Error)
+        .count();
+    let warnings = diagnostics
+        .iter()
+        .filter(|(severity, _)| *severity == Severity::Warning)
+        .count();
+
+    let title = match warnings {
+        0 => format!("Reload failed with {errors} error(s)"),
+        _ => format!("Reload failed with {errors} error(s) and {warnings} warning(s)"),
+    };
+
+    let action = |label: &'static str, message: Message| {
+        button(text(label).size(12))
+            .padding([2, 8])
+            .style(button::secondary)
+            .on_press(message)
+    };
+
+    let header = row![
+        text(title)
+            .size(13)
+            .font(Font::MONOSPACE)
+            .width(Length::Fill)
+            .style(|theme: &Theme| text::Style {
+                color: Some(theme.palette().danger),
+            }),
+        action("Copy", Message::Copy),
+        action("Retry", Message::Retry),
+        action(if collapsed { "Show" } else { "Hide" }, Message::Toggle),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    let mut content = column![header].spacing(8);
+
+    if !collapsed {
+        let diagnostics = diagnostics.into_iter().map(|(severity, rendered)| {
+            text(rendered)
+                .size(12)
+                .font(Font::MONOSPACE)
+                .style(move |theme: &Theme| text::Style {
+                    color: Some(color(theme, severity)),
+                })
+                .into()
+        });
+
+        content = content.push(
+            container(scrollable(column(diagnostics).spacing(8)).width(Length::Fill))
+                .max_height(240),
+        );
+    }
+
+    container(content)
+        .padding(12)
+        .width(Length::Fill)
+        .style(|theme: &Theme| {
+            let palette = theme.palette();
+
+            container::Style {
+                background: Some(
+                    Color {
+                        a: 0.95,
+                        ..palette.background
+                    }
+                    .into(),
+                ),
+                border: Border {
+                    color: palette.danger,
+                    width: 1.0,
+                    radius: 4.0.into(),
+                },
+                text_color: Some(palette.text),
+                ..container::Style::default()
+            }
+        })
+        .into()
+}
+
+fn diagnostics(error: &crate::Error) -> Vec<(Severity, String)> {
+    match error {
+        crate::Error::CargoComponent(failure) if !failure.diagnostics.is_empty() => failure
+            .diagnostics
+            .iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.rendered.clone()))
+            .collect(),
+        _ => vec![(Severity::Error, error.to_string())],
+    }
+}
+
+fn report(error: &crate::Error) -> String {
+    diagnostics(error)
+        .into_iter()
+        .map(|(_, rendered)| rendered)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn color(theme: &Theme, severity: Severity) -> Color {
+    let palette = theme.palette();
+
+    match severity {
+        Severity::Error => palette.danger,
+        Severity::Warning => palette.warning,
+        Severity::Note => palette.primary,
+        Severity::Help => palette.success,
+    }
+}
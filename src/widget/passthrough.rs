@@ -0,0 +1,39 @@
+use std::marker::PhantomData;
+
+use crate::Element;
+use crate::widget::Id;
+
+/// The initial view as it is, since nothing is hot reloaded without
+/// the `hot` feature.
+pub struct Thawing<'a, Message, Data = ()> {
+    initial: Element<'a, Message>,
+    state: PhantomData<&'a Data>,
+}
+
+impl<'a, Message, Data> Thawing<'a, Message, Data> {
+    pub fn from_view(
+        element: impl Into<Element<'a, Message>>,
+        _file: &'static str,
+        _line: u32,
+        _column: u32,
+    ) -> Self {
+        Self {
+            initial: element.into(),
+            state: PhantomData,
+        }
+    }
+
+    pub fn id(self, _id: impl Into<Id>) -> Self {
+        self
+    }
+
+    pub fn state(self, _state: &Data) -> Self {
+        self
+    }
+}
+
+impl<'a, Message, Data> From<Thawing<'a, Message, Data>> for Element<'a, Message> {
+    fn from(widget: Thawing<'a, Message, Data>) -> Self {
+        widget.initial
+    }
+}
@@ -1,192 +1,99 @@
-use std::path::PathBuf;
+use std::cell::RefCell;
+use std::marker::PhantomData;
 use std::sync::Arc;
 
+use iced_core::overlay::Group;
 use iced_core::widget::{Operation, Tree};
 use iced_core::{Clipboard, Event, Layout, Rectangle, Shell};
 use iced_core::{layout, mouse, renderer};
 
 use crate::Element;
-use crate::{guest, runtime};
-
-pub struct Error<Message> {
-    element: Element<'static, Message>,
-}
-
-impl<Message> Error<Message> {
-    pub fn new(error: crate::Error) -> Self {
-        let element = failed(&error);
-
-        Self { element }
-    }
-}
+use crate::task::Caller;
+use crate::widget::Id;
+use crate::widget::overlay;
+use crate::widget::panel::Panel;
+use crate::{codec, guest, runtime, update};
 
 pub enum State<Message> {
     Loading {
         bytes: Arc<Vec<u8>>,
-        caller: PathBuf,
+        /// The format `bytes` were encoded with.
+        codec: codec::Format,
+        caller: Caller,
+        id: Option<Id>,
     },
-    Loaded(Result<Inner<Message>, Error<Message>>),
+    Loaded(Result<Inner<Message>, Panel>),
 }
 
 pub struct Inner<Message> {
     runtime: runtime::Runtime<'static>,
-    element: Result<Element<'static, guest::Message>, Error<Message>>,
-    mapper: Box<dyn Fn(guest::Message) -> Message>,
+    id: Option<Id>,
+    /// The last element the guest rendered successfully.
+    element: Option<Element<'static, guest::Message>>,
+    /// Whether a view of the runtime of `element` failed since, which drops
+    /// its closures, so its messages are ignored until a view succeeds.
+    stale: bool,
+    error: Option<Panel>,
+    /// A failure to map a message of an overlay, shown on the next update.
+    failure: RefCell<Option<crate::Error>>,
     bytes: Arc<Vec<u8>>,
     invalidated: bool,
+    /// The generation of the last reload applied.
+    generation: u64,
+    message: PhantomData<Message>,
 }
 
 impl<Message> Inner<Message> {
     pub fn engine(&self) -> runtime::Engine<'static> {
         self.runtime.engine()
     }
-}
-
-impl<Message> Inner<Message>
-where
-    Message: serde::de::DeserializeOwned + 'static,
-{
-    pub fn diff(&self, tree: &mut Tree) {
-        match &self.element {
-            Ok(element) => element.as_widget().diff(tree),
-            Err(error) => error.element.as_widget().diff(tree),
-        }
-    }
-
-    pub fn layout(
-        &self,
-        tree: &mut Tree,
-        renderer: &iced_widget::Renderer,
-        limits: &layout::Limits,
-    ) -> layout::Node {
-        match &self.element {
-            Ok(element) => element.as_widget().layout(tree, renderer, limits),
-            Err(error) => error.element.as_widget().layout(tree, renderer, limits),
-        }
-    }
-
-    pub fn operate(
-        &self,
-        tree: &mut Tree,
-        layout: Layout<'_>,
-        renderer: &iced_widget::Renderer,
-        operation: &mut dyn Operation,
-    ) {
-        match &self.element {
-            Ok(element) => element
-                .as_widget()
-                .operate(tree, layout, renderer, operation),
-            Err(error) => error
-                .element
-                .as_widget()
-                .operate(tree, layout, renderer, operation),
-        }
-    }
-
-    pub fn update(
-        &mut self,
-        tree: &mut Tree,
-        event: &Event,
-        layout: Layout<'_>,
-        cursor: mouse::Cursor,
-        renderer: &iced_widget::Renderer,
-        clipboard: &mut dyn Clipboard,
-        shell: &mut Shell<'_, Message>,
-        viewport: &Rectangle,
-    ) {
-        if self.invalidated {
-            shell.request_redraw();
-            self.invalidated = false;
-        }
 
-        match &mut self.element {
+    fn view(&mut self) -> Result<(), crate::Error> {
+        match self.runtime.view(&self.bytes) {
             Ok(element) => {
-                let mut messages = vec![];
-                let mut guest = Shell::new(&mut messages);
+                self.element = Some(element);
+                self.error = None;
+                self.stale = false;
 
-                element.as_widget_mut().update(
-                    tree, event, layout, cursor, renderer, clipboard, &mut guest, viewport,
-                );
+                Ok(())
+            }
+            Err(error) => {
+                self.fail(error.clone());
+                self.stale = self.element.is_some();
 
-                let runtime = self.runtime.state();
-                shell.merge(guest, move |message| {
-                    runtime.call(message.closure, message.data)
-                });
+                Err(error)
             }
-            Err(error) => error.element.as_widget_mut().update(
-                tree, event, layout, cursor, renderer, clipboard, shell, viewport,
-            ),
         }
     }
 
-    pub fn mouse_interaction(
-        &self,
-        tree: &Tree,
-        layout: Layout<'_>,
-        cursor: mouse::Cursor,
-        viewport: &Rectangle,
-        renderer: &iced_widget::Renderer,
-    ) -> mouse::Interaction {
-        match &self.element {
-            Ok(element) => element
-                .as_widget()
-                .mouse_interaction(tree, layout, cursor, viewport, renderer),
-            Err(error) => error
-                .element
-                .as_widget()
-                .mouse_interaction(tree, layout, cursor, viewport, renderer),
+    /// Makes the current component available to `#[thawing::update]`.
+    fn register(&self) {
+        if let Some(id) = &self.id {
+            update::register(id, self.runtime.state());
         }
     }
 
-    pub fn draw(
-        &self,
-        tree: &Tree,
-        renderer: &mut iced_widget::Renderer,
-        theme: &iced_widget::Theme,
-        style: &renderer::Style,
-        layout: Layout<'_>,
-        cursor: mouse::Cursor,
-        viewport: &Rectangle,
-    ) {
-        match &self.element {
-            Ok(element) => element
-                .as_widget()
-                .draw(tree, renderer, theme, style, layout, cursor, viewport),
-            Err(error) => error
-                .element
-                .as_widget()
-                .draw(tree, renderer, theme, style, layout, cursor, viewport),
-        }
+    /// Shows the error as a collapsed banner while a working element is
+    /// still around, or as the whole panel otherwise.
+    fn fail(&mut self, error: crate::Error) {
+        self.error = Some(Panel::new(error, self.element.is_some()));
     }
+}
 
-    pub fn overlay<'b>(
-        &'b mut self,
-        tree: &'b mut Tree,
-        layout: Layout<'b>,
-        renderer: &iced_widget::Renderer,
-        viewport: &iced_core::Rectangle,
-        translation: iced_core::Vector,
-    ) -> Option<iced_core::overlay::Element<'b, Message, iced_widget::Theme, iced_widget::Renderer>>
-    {
-        match &mut self.element {
-            Ok(element) => element
-                .as_widget_mut()
-                .overlay(tree, layout, renderer, viewport, translation)
-                .map(|overlay| overlay.map(&self.mapper)),
-            Err(error) => {
-                error
-                    .element
-                    .as_widget_mut()
-                    .overlay(tree, layout, renderer, viewport, translation)
-            }
+impl<Message> Drop for Inner<Message> {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            update::unregister(id, &self.runtime.state());
         }
     }
 }
 
 impl<Message> State<Message> {
     pub fn new(
+        id: &Option<Id>,
         bytes: &Result<Arc<Vec<u8>>, crate::Error>,
-        caller: &Result<PathBuf, crate::Error>,
+        codec: codec::Format,
+        caller: &Result<Caller, crate::Error>,
     ) -> Self {
         let bytes = match bytes {
             Ok(bytes) => Arc::clone(bytes),
@@ -198,14 +105,36 @@ impl<Message> State<Message> {
             Err(error) => return Self::failed(error),
         };
 
-        Self::Loading { bytes, caller }
+        Self::Loading {
+            bytes,
+            codec,
+            caller,
+            id: id.clone(),
+        }
+    }
+
+    /// The format the state must be encoded with, which is the one of the
+    /// runtime once loaded.
+    pub fn codec(&self, id: Option<&Id>) -> codec::Format {
+        match self {
+            State::Loaded(Ok(inner)) => inner.runtime.current().codec,
+            _ => codec::of(id),
+        }
+    }
+
+    /// The engine of the loaded runtime, to instantiate a reload with.
+    pub fn engine(&self) -> Option<runtime::Engine<'static>> {
+        match self {
+            State::Loaded(Ok(inner)) => Some(inner.engine()),
+            _ => None,
+        }
     }
 
     pub fn failed(error: &crate::Error) -> Self {
-        Self::Loaded(Err(Error::new(error.clone())))
+        Self::Loaded(Err(Panel::new(error.clone(), false)))
     }
 
-    pub fn error(&mut self, error: Option<Error<Message>>) {
+    pub fn error(&mut self, error: Option<crate::Error>) {
         let error = if let Some(error) = error {
             error
         } else {
@@ -213,24 +142,59 @@ impl<Message> State<Message> {
         };
 
         match self {
-            State::Loading { .. } => *self = State::Loaded(Err(error)),
-            State::Loaded(Err(previous)) => *previous = error,
-            State::Loaded(Ok(inner)) => inner.element = Err(error),
+            State::Loading { .. } | State::Loaded(Err(_)) => *self = Self::failed(&error),
+            State::Loaded(Ok(inner)) => inner.fail(error),
         }
     }
 
-    pub fn reload(&mut self, state: Result<runtime::State<'static>, crate::Error>) {
-        if let State::Loaded(Ok(inner)) = self {
-            let timer = std::time::Instant::now();
-            if let Err(error) = inner.runtime.reload(state) {
-                tracing::error!("Failed to reload: {error:?}");
-                inner.element = Err(Error::new(error));
-                return;
-            }
+    pub fn patch(&mut self, literals: &[runtime::Literal]) -> Result<(), crate::Error> {
+        let State::Loaded(Ok(inner)) = self else {
+            return Err(crate::Error::Runtime(runtime::Error::NotLoaded));
+        };
+
+        let timer = std::time::Instant::now();
+        inner.runtime.patch(literals)?;
+
+        if let Err(error) = inner.view() {
+            tracing::error!("Failed to view patched component: {error:?}");
+            return Err(error);
+        }
+
+        inner.invalidated = true;
+        tracing::info!(
+            "Patched {} literal(s) in {:?}",
+            literals.len(),
+            timer.elapsed()
+        );
+
+        Ok(())
+    }
+
+    pub fn rollback(&mut self, steps: usize) -> Result<(), crate::Error> {
+        let State::Loaded(Ok(inner)) = self else {
+            return Err(crate::Error::Runtime(runtime::Error::NotLoaded));
+        };
+
+        inner.runtime.rollback(steps)?;
 
-            inner.element = inner.runtime.view(&inner.bytes).map_err(Error::new);
-            inner.invalidated = true;
-            tracing::info!("Reloaded in {:?}", timer.elapsed());
+        let stale = inner.stale;
+        if let Err(error) = inner.view() {
+            inner.runtime.rollback(1)?;
+            inner.stale = stale;
+            return Err(error);
+        }
+
+        inner.register();
+        inner.invalidated = true;
+        tracing::info!("Rolled back {steps} reload(s)");
+
+        Ok(())
+    }
+
+    fn guest(&self) -> Option<&Element<'static, guest::Message>> {
+        match self {
+            State::Loaded(Ok(inner)) => inner.element.as_ref(),
+            _ => None,
         }
     }
 }
@@ -239,23 +203,109 @@ impl<Message> State<Message>
 where
     Message: serde::de::DeserializeOwned + 'static,
 {
-    pub fn loaded(runtime: runtime::Runtime<'static>, bytes: &Arc<Vec<u8>>) -> Self {
-        let bytes = Arc::clone(bytes);
-        let element = runtime.view(&bytes).map_err(Error::new);
-        let mapper = {
-            let runtime = runtime.state();
-            Box::new(move |message: guest::Message| runtime.call(message.closure, message.data))
+    /// Replaces the loading, or failed, state with the runtime, returning
+    /// whether its first view succeeded.
+    pub fn load(
+        &mut self,
+        runtime: runtime::Runtime<'static>,
+        id: &Id,
+    ) -> Result<(), crate::Error> {
+        let (bytes, id, deferred) = match self {
+            State::Loading {
+                bytes, codec, id, ..
+            } => {
+                // The state was encoded with another format than the one of the
+                // runtime, so it is left for the next diff to encode again and view
+                if *codec != runtime.current().codec && !bytes.is_empty() {
+                    (Arc::new(Vec::new()), id.clone(), true)
+                } else {
+                    (Arc::clone(bytes), id.clone(), false)
+                }
+            }
+            // The state of a failed first build was dropped along with it
+            State::Loaded(Err(_)) => (Arc::new(Vec::new()), Some(id.clone()), true),
+            State::Loaded(Ok(_)) => return Ok(()),
         };
 
-        let inner = Inner {
+        let mut inner = Inner {
             runtime,
-            element,
-            mapper,
+            id,
+            element: None,
+            stale: false,
+            error: None,
+            failure: RefCell::new(None),
             bytes,
             invalidated: true,
+            generation: 0,
+            message: PhantomData,
         };
+        let result = if deferred { Ok(()) } else { inner.view() };
+        inner.register();
 
-        Self::Loaded(Ok(inner))
+        *self = Self::Loaded(Ok(inner));
+
+        result
+    }
+
+    /// Reloads the runtime with the state of the given one, or loads it
+    /// whole when the first build failed or has not loaded yet.
+    pub fn reload(
+        &mut self,
+        runtime: Result<runtime::Runtime<'static>, crate::Error>,
+        id: &Id,
+        generation: u64,
+    ) -> Result<(), crate::Error> {
+        let inner = match self {
+            State::Loaded(Ok(inner)) => inner,
+            State::Loading { .. } | State::Loaded(Err(_)) => {
+                let result = match runtime {
+                    Ok(runtime) => self.load(runtime, id),
+                    Err(error) => {
+                        *self = Self::failed(&error);
+                        Err(error)
+                    }
+                };
+
+                if let State::Loaded(Ok(inner)) = self {
+                    inner.generation = generation;
+                }
+
+                return result;
+            }
+        };
+
+        if generation < inner.generation {
+            tracing::info!("Ignoring stale reload of generation {generation}");
+            return Ok(());
+        }
+        inner.generation = generation;
+
+        let timer = std::time::Instant::now();
+        if let Err(error) = inner
+            .runtime
+            .reload(runtime.map(runtime::Runtime::into_state))
+        {
+            tracing::error!("Failed to reload: {error:?}");
+            inner.fail(error.clone());
+            return Err(error);
+        }
+
+        let stale = inner.stale;
+        if let Err(error) = inner.view() {
+            tracing::error!("Failed to view reloaded component: {error:?}");
+
+            // Keep the last working component, since its element is still shown
+            // and its closures were left untouched
+            inner.runtime.revert()?;
+            inner.stale = stale;
+            return Err(error);
+        }
+
+        inner.register();
+        inner.invalidated = true;
+        tracing::info!("Reloaded in {:?}", timer.elapsed());
+
+        Ok(())
     }
 
     pub fn diff(
@@ -264,25 +314,26 @@ where
         initial: &Element<'_, Message>,
         tree: &mut Tree,
     ) {
-        match self {
-            State::Loading { .. } => initial.as_widget().diff(tree),
-            State::Loaded(Err(error)) => error.element.as_widget().diff(tree),
-            State::Loaded(Ok(inner)) => {
-                match other {
-                    Err(error) => {
-                        inner.element = Err(Error::new(error.clone()));
-                    }
-                    Ok(other) => {
-                        if !Arc::ptr_eq(&inner.bytes, other) {
-                            inner.bytes = Arc::clone(other);
-                            inner.element = inner.runtime.view(other).map_err(Error::new);
-                        }
+        if let State::Loaded(Ok(inner)) = self {
+            match other {
+                Err(error) => inner.fail(error.clone()),
+                Ok(other) => {
+                    // Every view encodes the state anew, so only its contents tell,
+                    // unless the runtime was loaded without viewing it
+                    let unviewed = inner.element.is_none() && inner.error.is_none();
+
+                    if inner.bytes != *other || unviewed {
+                        inner.bytes = Arc::clone(other);
+                        let _ = inner.view();
                     }
                 }
-
-                inner.diff(tree)
             }
         }
+
+        match self.guest() {
+            Some(element) => element.as_widget().diff(tree),
+            None => initial.as_widget().diff(tree),
+        }
     }
 
     pub fn layout(
@@ -292,10 +343,9 @@ where
         renderer: &iced_widget::Renderer,
         limits: &layout::Limits,
     ) -> layout::Node {
-        match self {
-            State::Loaded(Err(error)) => error.element.as_widget().layout(tree, renderer, limits),
-            State::Loading { .. } => initial.as_widget().layout(tree, renderer, limits),
-            State::Loaded(Ok(inner)) => inner.layout(tree, renderer, limits),
+        match self.guest() {
+            Some(element) => element.as_widget().layout(tree, renderer, limits),
+            None => initial.as_widget().layout(tree, renderer, limits),
         }
     }
 
@@ -307,15 +357,13 @@ where
         renderer: &iced_widget::Renderer,
         operation: &mut dyn Operation,
     ) {
-        match self {
-            State::Loaded(Err(error)) => error
-                .element
+        match self.guest() {
+            Some(element) => element
                 .as_widget()
                 .operate(tree, layout, renderer, operation),
-            State::Loading { .. } => initial
+            None => initial
                 .as_widget()
                 .operate(tree, layout, renderer, operation),
-            State::Loaded(Ok(inner)) => inner.operate(tree, layout, renderer, operation),
         }
     }
 
@@ -331,18 +379,53 @@ where
         shell: &mut Shell<'_, Message>,
         viewport: &Rectangle,
     ) {
-        match self {
-            State::Loaded(Err(error)) => error.element.as_widget_mut().update(
+        let State::Loaded(Ok(inner)) = self else {
+            return initial.as_widget_mut().update(
                 tree, event, layout, cursor, renderer, clipboard, shell, viewport,
-            ),
-            State::Loading { .. } => initial.as_widget_mut().update(
-                tree, event, layout, cursor, renderer, clipboard, shell, viewport,
-            ),
-            State::Loaded(Ok(inner)) => {
-                inner.update(
-                    tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+            );
+        };
+
+        if inner.invalidated {
+            shell.request_redraw();
+            inner.invalidated = false;
+        }
+
+        if let Some(error) = inner.failure.take() {
+            tracing::error!("Failed to map a message of the guest: {error:?}");
+            inner.fail(error);
+            shell.invalidate_layout();
+        }
+
+        match &mut inner.element {
+            Some(element) => {
+                let mut messages = vec![];
+                let mut guest = Shell::new(&mut messages);
+
+                element.as_widget_mut().update(
+                    tree, event, layout, cursor, renderer, clipboard, &mut guest, viewport,
                 );
+
+                overlay::merge(shell, &guest);
+
+                if inner.stale {
+                    if !messages.is_empty() {
+                        tracing::warn!("Ignoring messages of an element that failed to view");
+                    }
+
+                    return;
+                }
+
+                let runtime = inner.runtime.state();
+                if let Err(error) = overlay::relay(&runtime, messages, shell) {
+                    tracing::error!("Failed to map a message of the guest: {error:?}");
+                    inner.fail(error);
+                    shell.invalidate_layout();
+                    shell.request_redraw();
+                }
             }
+            None => initial.as_widget_mut().update(
+                tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+            ),
         }
     }
 
@@ -355,17 +438,13 @@ where
         viewport: &Rectangle,
         renderer: &iced_widget::Renderer,
     ) -> mouse::Interaction {
-        match self {
-            State::Loaded(Err(error)) => error
-                .element
+        match self.guest() {
+            Some(element) => element
                 .as_widget()
                 .mouse_interaction(tree, layout, cursor, viewport, renderer),
-            State::Loading { .. } => initial
+            None => initial
                 .as_widget()
                 .mouse_interaction(tree, layout, cursor, viewport, renderer),
-            State::Loaded(Ok(inner)) => {
-                inner.mouse_interaction(tree, layout, cursor, viewport, renderer)
-            }
         }
     }
 
@@ -380,22 +459,19 @@ where
         cursor: mouse::Cursor,
         viewport: &Rectangle,
     ) {
-        match self {
-            State::Loaded(Err(error)) => error
-                .element
+        match self.guest() {
+            Some(element) => element
                 .as_widget()
                 .draw(tree, renderer, theme, style, layout, cursor, viewport),
-            State::Loading { .. } => initial
+            None => initial
                 .as_widget()
                 .draw(tree, renderer, theme, style, layout, cursor, viewport),
-            State::Loaded(Ok(inner)) => {
-                inner.draw(tree, renderer, theme, style, layout, cursor, viewport)
-            }
         }
     }
 
     pub fn overlay<'b>(
         &'b mut self,
+        id: Option<&Id>,
         initial: &'b mut Element<'_, Message>,
         tree: &'b mut Tree,
         layout: Layout<'b>,
@@ -404,25 +480,88 @@ where
         translation: iced_core::Vector,
     ) -> Option<iced_core::overlay::Element<'b, Message, iced_widget::Theme, iced_widget::Renderer>>
     {
-        match self {
-            State::Loaded(Err(error)) => {
-                error
-                    .element
+        let (panel, overlay) = match self {
+            State::Loading { .. } => (
+                None,
+                initial
                     .as_widget_mut()
-                    .overlay(tree, layout, renderer, viewport, translation)
-            }
-            State::Loading { .. } => {
+                    .overlay(tree, layout, renderer, viewport, translation),
+            ),
+            State::Loaded(Err(panel)) => (
+                Some(panel),
                 initial
                     .as_widget_mut()
-                    .overlay(tree, layout, renderer, viewport, translation)
-            }
-            State::Loaded(Ok(inner)) => {
-                inner.overlay(tree, layout, renderer, viewport, translation)
+                    .overlay(tree, layout, renderer, viewport, translation),
+            ),
+            State::Loaded(Ok(Inner {
+                runtime,
+                element,
+                stale,
+                error,
+                failure,
+                ..
+            })) => {
+                let overlay = match element {
+                    // The messages of a stale overlay could not be mapped
+                    Some(_) if *stale => None,
+                    Some(element) => element
+                        .as_widget_mut()
+                        .overlay(tree, layout, renderer, viewport, translation)
+                        .map(|overlay| overlay::Guest::new(overlay, runtime.current(), failure)),
+                    None => initial.as_widget_mut().overlay(
+                        tree,
+                        layout,
+                        renderer,
+                        viewport,
+                        translation,
+                    ),
+                };
+
+                (error.as_mut(), overlay)
             }
-        }
+        };
+
+        let Some(panel) = panel else {
+            return overlay;
+        };
+
+        let children = overlay
+            .into_iter()
+            .chain([panel.overlay(id, layout.bounds(), translation)])
+            .collect();
+
+        Some(Group::with_children(children).overlay())
     }
 }
 
-fn failed<'a, Message>(text: impl ToString) -> Element<'a, Message> {
-    iced_widget::text(text.to_string()).size(12).into()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_failed_first_build_is_reloaded_into_a_new_runtime() {
+        let mut state = State::<()>::failed(&crate::Error::RecvFailed);
+
+        // Without an engine to reuse, the reload instantiates a whole runtime
+        assert!(state.engine().is_none());
+
+        // Later failures keep it replaceable by that runtime
+        state.error(Some(crate::Error::SendFailed));
+        assert!(matches!(state, State::Loaded(Err(_))));
+        assert!(state.engine().is_none());
+    }
+
+    #[test]
+    fn a_failed_first_build_is_reloadable_but_has_no_history() {
+        let mut state = State::<()>::failed(&crate::Error::RecvFailed);
+
+        let result = state.reload(Err(crate::Error::SendFailed), &Id::new("thawing"), 1);
+        assert!(matches!(result, Err(crate::Error::SendFailed)));
+        assert!(matches!(state, State::Loaded(Err(_))));
+
+        assert!(matches!(
+            state.rollback(1),
+            Err(crate::Error::Runtime(runtime::Error::NotLoaded))
+        ));
+    }
 }